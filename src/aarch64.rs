@@ -0,0 +1,68 @@
+//! Hand-written NEON kernels for `axpy![aarch64: ...]`, for when the auto-vectorizer cannot be
+//! relied upon.
+//!
+//! NEON is part of the baseline of every AArch64 target, so there is nothing to detect. SVE
+//! kernels, with predicated remainders, would follow the same pattern, but the SVE intrinsics are
+//! not yet available in `core::arch`; until they are, the remainder is done one element at a time.
+//!
+//! Only statements prefixed with `aarch64:` reach these kernels, and only sums of scaled `f32` or
+//! `f64` slices assigned by `=`, `+=` or `-=` may be: the macro cannot see the element type of an
+//! unprefixed statement, so that keeps the generic loop. There are no NEON reductions either;
+//! `dot!` and the others are the generic ones (`dot![lanes: ...]` being the way to give the
+//! compiler independent accumulators to vectorize).
+
+use core::arch::aarch64::*;
+use core::cmp::min;
+use __rt::combine as scalar;
+
+/// An element type with AArch64 kernels, i.e. `f32` or `f64`.
+pub trait Simd: Copy {
+    /// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]`, or `y += ...` if `accumulate`, over the shortest
+    /// length.
+    fn combine<const N: usize>(y: &mut [Self], accumulate: bool, a: [Self; N], x: [&[Self]; N]);
+}
+
+/// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]` (or `y += ...`), see `Simd::combine`.
+#[inline]
+pub fn combine<T: Simd, const N: usize>(y: &mut [T], accumulate: bool, a: [T; N], x: [&[T]; N]) {
+    T::combine(y, accumulate, a, x)
+}
+
+// The vector loop handles two registers of W elements at a time, to keep both FMA pipes busy,
+// and the remaining elements are done one at a time.
+macro_rules! simd {
+    ($t:ident, $w:expr, $dup:ident, $load:ident, $store:ident, $fma:ident) => {
+        impl Simd for $t {
+            #[inline]
+            fn combine<const N: usize>(y: &mut [$t], accumulate: bool, a: [$t; N], x: [&[$t]; N]) {
+                let n = x.iter().fold(y.len(), |n, x| min(n, x.len()));
+                let m = n - n % (2 * $w);
+                unsafe {
+                    let mut av = [$dup(0.); N];
+                    for k in 0..N {
+                        av[k] = $dup(a[k]);
+                    }
+                    let mut i = 0;
+                    while i < m {
+                        let (mut s, mut t) = if accumulate {
+                            ($load(y.as_ptr().add(i)), $load(y.as_ptr().add(i + $w)))
+                        } else {
+                            ($dup(0.), $dup(0.))
+                        };
+                        for k in 0..N {
+                            s = $fma(s, av[k], $load(x[k].as_ptr().add(i)));
+                            t = $fma(t, av[k], $load(x[k].as_ptr().add(i + $w)));
+                        }
+                        $store(y.as_mut_ptr().add(i), s);
+                        $store(y.as_mut_ptr().add(i + $w), t);
+                        i += 2 * $w;
+                    }
+                }
+                scalar(&mut y[m..n], accumulate, a, x.map(|x| &x[m..n]));
+            }
+        }
+    };
+}
+
+simd!(f32, 4, vdupq_n_f32, vld1q_f32, vst1q_f32, vfmaq_f32);
+simd!(f64, 2, vdupq_n_f64, vld1q_f64, vst1q_f64, vfmaq_f64);
@@ -0,0 +1,176 @@
+//! Streaming accumulators for reductions over data that arrives in chunks.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Running inner product, fed one chunk at a time by `dot![acc += x, y]`.
+///
+/// The per-chunk partial sums are combined with Kahan compensation, so splitting a reduction into
+/// many chunks does not cost accuracy, and accumulators over disjoint parts of the data can be
+/// combined with `merge`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DotAcc<T> {
+    sum: T,
+    comp: T,
+}
+
+impl<T> DotAcc<T> where T: Copy + Default + Add<Output = T> + Sub<Output = T> {
+    /// An empty accumulator.
+    pub fn new() -> DotAcc<T> {
+        DotAcc { sum: T::default(), comp: T::default() }
+    }
+
+    /// Adds the partial result of one chunk.
+    #[inline]
+    pub fn update(&mut self, partial: T) {
+        let y = partial - self.comp;
+        let t = self.sum + y;
+        self.comp = (t - self.sum) - y;
+        self.sum = t;
+    }
+
+    /// Adds everything accumulated by `other`.
+    pub fn merge(&mut self, other: DotAcc<T>) {
+        self.update(other.sum);
+        self.update(T::default() - other.comp);
+    }
+
+    /// The reduction over all chunks seen so far.
+    pub fn finish(self) -> T {
+        self.sum
+    }
+}
+
+/// A floating-point type that `Stats` can divide by a count.
+pub trait Real: Copy + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    fn from_count(n: usize) -> Self;
+}
+
+impl Real for f32 {
+    #[inline(always)]
+    fn from_count(n: usize) -> f32 { n as f32 }
+}
+
+impl Real for f64 {
+    #[inline(always)]
+    fn from_count(n: usize) -> f64 { n as f64 }
+}
+
+/// A floating-point type whose rounding errors `dot![bound: ...]` can estimate.
+pub trait Roundoff: Real + PartialOrd + Neg<Output = Self> {
+    /// Half the distance from 1 to the next larger number, which bounds the relative error of
+    /// each rounded operation.
+    fn unit_roundoff() -> Self;
+}
+
+impl Roundoff for f32 {
+    #[inline(always)]
+    fn unit_roundoff() -> f32 { f32::EPSILON / 2. }
+}
+
+impl Roundoff for f64 {
+    #[inline(always)]
+    fn unit_roundoff() -> f64 { f64::EPSILON / 2. }
+}
+
+/// Count, mean and variance of a sequence, as returned by `stats![x]`.
+///
+/// Values are added with Welford's update, which, unlike the textbook sum of squares, does not
+/// cancel catastrophically when the mean is large compared to the spread. `stats!` keeps one
+/// state per vector lane and combines them with `merge` at the end, and `stats![acc += x]` merges
+/// the statistics of a chunk into a running `acc`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats<T> {
+    count: usize,
+    mean: T,
+    m2: T,
+}
+
+impl<T: Real> Stats<T> {
+    /// The statistics of nothing.
+    pub fn new() -> Stats<T> {
+        Stats { count: 0, mean: T::default(), m2: T::default() }
+    }
+
+    /// Adds one value.
+    #[inline]
+    pub fn push(&mut self, v: T) {
+        self.count += 1;
+        let d = v - self.mean;
+        self.mean = self.mean + d / T::from_count(self.count);
+        self.m2 = self.m2 + d * (v - self.mean);
+    }
+
+    /// Adds everything accumulated by `other` (Chan et al.'s pairwise combination).
+    pub fn merge(&mut self, other: Stats<T>) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other;
+            return;
+        }
+        let n = self.count + other.count;
+        let (na, nb, nn) = (T::from_count(self.count), T::from_count(other.count), T::from_count(n));
+        let d = other.mean - self.mean;
+        self.mean = self.mean + d * nb / nn;
+        self.m2 = self.m2 + other.m2 + d * d * na * nb / nn;
+        self.count = n;
+    }
+
+    /// The number of values.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The mean, zero for no values.
+    pub fn mean(&self) -> T {
+        self.mean
+    }
+
+    /// The population variance, the mean squared deviation from the mean.
+    pub fn variance(&self) -> T {
+        if self.count == 0 { T::default() } else { self.m2 / T::from_count(self.count) }
+    }
+
+    /// The sample variance, normalized by `count - 1` rather than `count`.
+    pub fn sample_variance(&self) -> T {
+        if self.count < 2 { T::default() } else { self.m2 / T::from_count(self.count - 1) }
+    }
+}
+
+/// The statistics of `iter`, updating `LANES` interleaved states in lockstep so that the loop
+/// vectorizes, then merging them.
+#[inline]
+pub(crate) fn stats<T: Real, I: Iterator<Item = T>>(mut iter: I) -> Stats<T> {
+    const LANES: usize = 4;
+    let (mut mean, mut m2) = ([T::default(); LANES], [T::default(); LANES]);
+    let mut rounds = 0;
+    let mut tail = Stats::new();
+    'rounds: loop {
+        let mut v = [T::default(); LANES];
+        for l in 0..LANES {
+            match iter.next() {
+                Some(x) => v[l] = x,
+                None => {
+                    v[..l].iter().for_each(|&x| tail.push(x));
+                    break 'rounds;
+                }
+            }
+        }
+        rounds += 1;
+        let inv = T::from_count(1) / T::from_count(rounds);
+        for l in 0..LANES {
+            let d = v[l] - mean[l];
+            mean[l] = mean[l] + d * inv;
+            m2[l] = m2[l] + d * (v[l] - mean[l]);
+        }
+    }
+    let mut s = Stats::new();
+    for l in 0..LANES {
+        s.merge(Stats { count: rounds, mean: mean[l], m2: m2[l] });
+    }
+    s.merge(tail);
+    s
+}
@@ -0,0 +1,16 @@
+/// Evaluates a linear combination at a single index, without a loop.
+///
+/// `let v = axpy_at![i; a*x + b*y - z];` takes the same right-hand sides as `axpy!`, and is the
+/// element `i` of the vector they describe, here `a * x[i] + b * y[i] - z[i]`, e.g. to spot-check
+/// an element of a statement's target against the very same expression:
+///     axpy![w = a*x + b*y - z];
+///     assert_eq!(w[7], axpy_at![7; a*x + b*y - z]);
+///
+/// Every slice operand is indexed directly, so `i` must be in bounds of all of them (or else this
+/// panics). Iterator, column and macro operands are not supported.
+#[macro_export]
+macro_rules! axpy_at {
+    [$i:expr; $($rest:tt)+] => {
+        { let i: usize = $i; axpy![! (emit (at i) __axpy_at = ()) () $($rest)* + .] }
+    };
+}
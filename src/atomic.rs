@@ -0,0 +1,133 @@
+//! Atomically updated targets, for `axpy![atomic: f += a*x]` from several threads at once.
+//!
+//! Every element of the target is read and updated by a single relaxed atomic operation:
+//! `fetch_add` for `+=`, `fetch_sub` for `-=` and `store` for `=`, so that threads accumulating
+//! into the same slice, e.g. scattering forces into a shared array, neither race nor lose each
+//! other's updates. (Relaxed operations are enough for that: the sum is complete once the threads
+//! are joined, which synchronizes with all of their updates.)
+//!
+//! Integer atomics such as `AtomicU32` or `AtomicI64` are targets as they are. Floats are stored in
+//! `AtomicF32` and `AtomicF64` as their bits, and added by a compare-and-swap loop; a slice of
+//! `AtomicU32` or `AtomicU64` holding float bits is viewed as one of them by `from_bits`.
+
+use core::sync::atomic::{AtomicI32, AtomicU32};
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicI64, AtomicU64};
+
+pub use core::sync::atomic::Ordering;
+#[doc(hidden)]
+pub use core::sync::atomic::Ordering::Relaxed;
+
+/// A value that is read and updated atomically, the element of an `atomic:` target.
+pub trait Atomic {
+    /// The type of the value.
+    type Value: Copy;
+    /// Reads the value.
+    fn load(&self, order: Ordering) -> Self::Value;
+    /// Writes `v`.
+    fn store(&self, v: Self::Value, order: Ordering);
+    /// Adds `v`, returning the previous value.
+    fn fetch_add(&self, v: Self::Value, order: Ordering) -> Self::Value;
+    /// Subtracts `v`, returning the previous value.
+    fn fetch_sub(&self, v: Self::Value, order: Ordering) -> Self::Value;
+}
+
+macro_rules! integer {
+    ($a:ty, $t:ty) => {
+        impl Atomic for $a {
+            type Value = $t;
+            #[inline(always)]
+            fn load(&self, order: Ordering) -> $t {
+                <$a>::load(self, order)
+            }
+            #[inline(always)]
+            fn store(&self, v: $t, order: Ordering) {
+                <$a>::store(self, v, order)
+            }
+            #[inline(always)]
+            fn fetch_add(&self, v: $t, order: Ordering) -> $t {
+                <$a>::fetch_add(self, v, order)
+            }
+            #[inline(always)]
+            fn fetch_sub(&self, v: $t, order: Ordering) -> $t {
+                <$a>::fetch_sub(self, v, order)
+            }
+        }
+    };
+}
+
+integer!(AtomicU32, u32);
+integer!(AtomicI32, i32);
+#[cfg(target_has_atomic = "64")]
+integer!(AtomicU64, u64);
+#[cfg(target_has_atomic = "64")]
+integer!(AtomicI64, i64);
+
+macro_rules! float {
+    ($(#[$attr:meta])* $f:ident, $bits:ident, $t:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Default)]
+        #[repr(transparent)]
+        pub struct $f($bits);
+
+        impl $f {
+            /// An atomic float of value `v`.
+            pub fn new(v: $t) -> $f {
+                $f($bits::new(v.to_bits()))
+            }
+
+            /// The floats whose bits are stored in `x`.
+            pub fn from_bits(x: &[$bits]) -> &[$f] {
+                // ($f is a transparent wrapper of $bits)
+                unsafe { core::slice::from_raw_parts(x.as_ptr() as *const $f, x.len()) }
+            }
+
+            /// The value, the float no longer being shared.
+            pub fn into_inner(self) -> $t {
+                <$t>::from_bits(self.0.into_inner())
+            }
+
+            /// Applies `f` to the value by a compare-and-swap loop, returning the previous value.
+            #[inline(always)]
+            fn update(&self, order: Ordering, f: impl Fn($t) -> $t) -> $t {
+                let old = self.0.fetch_update(order, Ordering::Relaxed, |b| Some(f(<$t>::from_bits(b)).to_bits()));
+                // (the closure never fails)
+                <$t>::from_bits(old.unwrap_or_else(|b| b))
+            }
+        }
+
+        impl Atomic for $f {
+            type Value = $t;
+            #[inline(always)]
+            fn load(&self, order: Ordering) -> $t {
+                <$t>::from_bits(self.0.load(order))
+            }
+            #[inline(always)]
+            fn store(&self, v: $t, order: Ordering) {
+                self.0.store(v.to_bits(), order)
+            }
+            #[inline(always)]
+            fn fetch_add(&self, v: $t, order: Ordering) -> $t {
+                self.update(order, |x| x + v)
+            }
+            #[inline(always)]
+            fn fetch_sub(&self, v: $t, order: Ordering) -> $t {
+                self.update(order, |x| x - v)
+            }
+        }
+
+        impl From<$t> for $f {
+            fn from(v: $t) -> $f {
+                $f::new(v)
+            }
+        }
+    };
+}
+
+float!(
+    /// An `f32` that is read and updated atomically, stored as its bits in an `AtomicU32`.
+    AtomicF32, AtomicU32, f32);
+#[cfg(target_has_atomic = "64")]
+float!(
+    /// An `f64` that is read and updated atomically, stored as its bits in an `AtomicU64`.
+    AtomicF64, AtomicU64, f64);
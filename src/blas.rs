@@ -0,0 +1,41 @@
+//! The common fixed shapes as plain generic functions, for call sites that cannot use a macro,
+//! e.g. when the operation is picked at run time or stored as a function pointer.
+//!
+//! Each is a single `axpy!`-generated loop, and like the macros they stop at the shorter operand.
+
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Mul};
+
+/// `y += a*x`.
+#[inline]
+pub fn axpy<T: Copy + Mul<Output = T> + AddAssign>(a: T, x: &[T], y: &mut [T]) {
+    axpy![truncate: y += a*x];
+}
+
+/// `y = a*x + b*y`.
+#[inline]
+pub fn axpby<T: Copy + Mul<Output = T> + Add<Output = T>>(a: T, x: &[T], b: T, y: &mut [T]) {
+    axpy![truncate: y = a*x + b*y];
+}
+
+/// `x = a*x`.
+#[inline]
+pub fn scal<T: Copy + Mul<Output = T>>(a: T, x: &mut [T]) {
+    axpy![truncate: x = a*x];
+}
+
+/// The inner product of `x` and `y`.
+#[inline]
+pub fn dot<T: Copy + Mul<Output = T> + Sum<T>>(x: &[T], y: &[T]) -> T {
+    dot![x, y]
+}
+
+/// `y += a*x` in single precision.
+pub fn saxpy(a: f32, x: &[f32], y: &mut [f32]) {
+    axpy(a, x, y)
+}
+
+/// `y += a*x` in double precision.
+pub fn daxpy(a: f64, x: &[f64], y: &mut [f64]) {
+    axpy(a, x, y)
+}
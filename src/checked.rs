@@ -0,0 +1,16 @@
+use core::fmt;
+
+/// An operation that overflowed in `axpy![checked_ops: ...]`.
+///
+/// The elements before `index` have been updated, the others are left as they were.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overflow {
+    /// The position of the element whose evaluation overflowed.
+    pub index: usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "arithmetic overflow at element {}", self.index)
+    }
+}
@@ -0,0 +1,543 @@
+//! The misuses the macros report with `compile_error!`, one doctest each, so that an arm that
+//! silently stops matching turns into a failing test. rustdoc only checks that each snippet fails
+//! to compile, not the message, which is quoted above it.
+//!
+//! A coefficient without its operator, `a x`, reported as "expected `+`, `-` or `*` before
+//! `x`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = a x + y];
+//! # }
+//! ```
+//!
+//! A trailing operator, reported as "the expression ends in a dangling operator":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = x + y +];
+//! # }
+//! ```
+//!
+//! The target scaling an operand, `z = z*x`, reported as "the target `z` is used as a
+//! coefficient":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = z*x];
+//! # }
+//! ```
+//!
+//! The target as a coefficient in a later term, reported as "the target `z` is used as a
+//! coefficient":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = x + z*y];
+//! # }
+//! ```
+//!
+//! The target as a negated coefficient, reported as "the target `z` is used as a coefficient":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = -z*x];
+//! # }
+//! ```
+//!
+//! The target as a subtracted coefficient, reported as "the target `z` is used as a
+//! coefficient":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = x - z*y];
+//! # }
+//! ```
+//!
+//! The target as the coefficient of a function, reported as "the target `z` is used as a
+//! coefficient":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = z*abs(x)];
+//! # }
+//! ```
+//!
+//! The target as the subtracted coefficient of a function, reported as "the target `z` is used
+//! as a coefficient":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = x - z*abs(y)];
+//! # }
+//! ```
+//!
+//! A scalar where a vector operand is expected, reported as "expected a vector operand, found":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = x + 2.];
+//! # }
+//! ```
+//!
+//! A parenthesized target without an assignment, reported as "expected an assignment to":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![(z) x + y];
+//! # }
+//! ```
+//!
+//! A sparse operand assigned with `=`, reported as "a sparse operand is added with `+=` or
+//! `-=`, not `=`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let (idx, vals) = ([0usize], [1f64]);
+//! axpy![z = sparse(idx, vals)];
+//! # }
+//! ```
+//!
+//! A divided combination with a coefficient, reported as "a divided combination cannot have a
+//! coefficient":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = a*(x - y) / 2.];
+//! # }
+//! ```
+//!
+//! A panel operand assigned to a plain target, reported as "panel operands need a panel
+//! target":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![z = x + panel(y, 2, 2, 2)];
+//! # }
+//! ```
+//!
+//! `rot` under a prefix, reported as "rot operands are supported in plain statements only":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![checked_fp: z = rot(x, 1)];
+//! # }
+//! ```
+//!
+//! `clamp` in `axpy_const!`, reported as "axpy_const!: clamp, min and max are not supported":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! const X: [f64; 2] = [1., 2.];
+//! const Z: [f64; 2] = axpy_const![clamp(X, 0., 1.)];
+//! let _ = Z;
+//! # }
+//! ```
+//!
+//! `hook` on a plain statement, reported as "hook: expected `tile`, `par` or a block with
+//! temporaries":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![hook(|_s, _n, _t| ()): z = x];
+//! # }
+//! ```
+//!
+//! An unknown stencil policy, reported as "stencil: expected `skip`, `clamp` or `wrap`, found
+//! `mirror`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![stencil(mirror): z = x(-1) + x(1)];
+//! # }
+//! ```
+//!
+//! A block statement that is neither a `let` nor an assignment, reported as "expected `let t =
+//! ...` or an assignment, found":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy!{let t = x + y; 2.;};
+//! # }
+//! ```
+//!
+//! A block that assigns nothing, reported as "a block with temporaries assigns nothing":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy!{let t = x + y;};
+//! # }
+//! ```
+//!
+//! `tail(overlap, ..)` with `+=`, reported as "tail(overlap, ..) needs `=`, not `+=`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![tail(overlap, 4): z += x];
+//! # }
+//! ```
+//!
+//! `tail(overlap, ..)` reading the target, reported as "tail(overlap, ..) cannot read the
+//! target `z`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![tail(overlap, 4): z = x + z];
+//! # }
+//! ```
+//!
+//! A function under an architecture prefix, reported as "support `=`, `+=` and `-=` of sums of
+//! scaled slices only":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![x86: z = abs(x)];
+//! # }
+//! ```
+//!
+//! A function in `axpy_const!`, reported as "axpy_const!: `Y` must be a plain array operand":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! const X: [f64; 2] = [1., 2.];
+//! const Y: [f64; 2] = [1., 2.];
+//! const Z: [f64; 2] = axpy_const![X + abs(Y)];
+//! let _ = Z;
+//! # }
+//! ```
+//!
+//! `*=` under `checked_ops:`, reported as "checked_ops: supports `=`, `+=` and `-=`, not `*=`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let (xi, mut zi) = ([1i32; 4], [0i32; 4]);
+//! axpy![checked_ops: zi *= xi];
+//! # }
+//! ```
+//!
+//! `*=` under `atomic:`, reported as "atomic: expected `+=`, `-=` or `=`, found `*=`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let xi = [1u32; 4];
+//! let zi: [core::sync::atomic::AtomicU32; 4] = Default::default();
+//! axpy![atomic: zi *= xi];
+//! # }
+//! ```
+//!
+//! A function of complex operands, reported as "complex operands cannot be functions":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let (xc, mut zc) = ([[1f64, 0.]; 4], [[0f64, 0.]; 4]);
+//! axpy![complex: zc = abs(xc)];
+//! # }
+//! ```
+//!
+//! `rev` under a prefix, reported as "rev(x) is supported in plain statements only":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![checked_fp: z = rev(x)];
+//! # }
+//! ```
+//!
+//! An iterator operand under `volatile:`, reported as "volatile: supports slice operands only,
+//! not `it`":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let it = x.iter().cloned();
+//! axpy![volatile: z = iter(it)];
+//! # }
+//! ```
+//!
+//! Powers of different variables in `polyval!`, reported as "polyval!: all terms must be powers
+//! of the same variable":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let (c0, c1, c2) = (1., 2., 3.);
+//! polyval![z = c0 + c1*x + c2*y^2];
+//! # }
+//! ```
+//!
+//! A leading term of `scan!` that does not scale the target, reported as "scan!: the leading
+//! term must scale the assigned vector":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let _ = scan![z = a*x + y; 0.];
+//! # }
+//! ```
+//!
+//! A `conv!` statement without a kernel, reported as "conv!: expected `y = k * x`, found":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! conv![z = x];
+//! # }
+//! ```
+//!
+//! A trailing term of `gemv!` that does not scale the target, reported as "gemv!: the trailing
+//! term must scale the assigned vector":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let m = [1f64; 16];
+//! gemv![z = panel(m, 4, 4, 4) * x + w];
+//! # }
+//! ```
+//!
+//! A trailing term of `spmv!` that does not scale the target, reported as "spmv!: the trailing
+//! term must scale the assigned vector":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! let (p, j, d) = ([0usize, 1], [0usize], [1f64]);
+//! spmv![z = csr(p, j, d) * x + w];
+//! # }
+//! ```
+//!
+//! A function in `tridiagmv!`, reported as "tridiagmv!: expected `a*shift(x, k)` or `a*x`,
+//! found":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! tridiagmv![z = y*x + abs(w)];
+//! # }
+//! ```
+//!
+//! `cuda(h):` under `reproducible:`, reported as "reproducible: cuBLAS does not guarantee an
+//! evaluation order":
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! axpy![reproducible: cuda(h): z = a*x + y];
+//! # }
+//! ```
+//!
+//! More distinct operands than a statement can hold, reported as "too many distinct operands
+//! (at most 64)":
+//!
+//! ```compile_fail
+//! # #![recursion_limit = "1024"]
+//! # #[macro_use] extern crate axpy;
+//! # fn main() {
+//! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+//! # let mut z = [0f64; 4];
+//! # let a = 2f64;
+//! # let [
+//! #     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+//! #     x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31,
+//! #     x32, x33, x34, x35, x36, x37, x38, x39, x40, x41, x42, x43, x44, x45, x46, x47,
+//! #     x48, x49, x50, x51, x52, x53, x54, x55, x56, x57, x58, x59, x60, x61, x62, x63,
+//! #     x64,
+//! # ] = [[1f64; 4]; 65];
+//! axpy![z = x0 + x1 + x2 + x3 + x4 + x5 + x6 + x7 + x8 + x9 + x10 + x11
+//!     + x12 + x13 + x14 + x15 + x16 + x17 + x18 + x19 + x20 + x21 + x22 + x23 + x24
+//!     + x25 + x26 + x27 + x28 + x29 + x30 + x31 + x32 + x33 + x34 + x35 + x36 + x37
+//!     + x38 + x39 + x40 + x41 + x42 + x43 + x44 + x45 + x46 + x47 + x48 + x49 + x50
+//!     + x51 + x52 + x53 + x54 + x55 + x56 + x57 + x58 + x59 + x60 + x61 + x62 + x63
+//!     + x64];
+//! # }
+//! ```
+
+#[cfg(feature = "rug")]
+mod rug {
+    //! The `rug:` prefix, with the `rug` feature.
+    //!
+    //! `clamp` under `rug:`, reported as "rug: clamp, min, max and abs are not supported":
+    //!
+    //! ```compile_fail
+    //! # #[macro_use] extern crate axpy;
+    //! # fn main() {
+    //! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+    //! # let mut z = [0f64; 4];
+    //! # let a = 2f64;
+    //! axpy![rug: z = clamp(x, 0., 1.)];
+    //! # }
+    //! ```
+    //!
+    //! `*=` under `rug:`, reported as "rug: expected `=`, `+=` or `-=`, found `*=`":
+    //!
+    //! ```compile_fail
+    //! # #[macro_use] extern crate axpy;
+    //! # fn main() {
+    //! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+    //! # let mut z = [0f64; 4];
+    //! # let a = 2f64;
+    //! axpy![rug: z *= x];
+    //! # }
+    //! ```
+    //!
+    //! A function under `rug:`, reported as "rug: supports the terms `± x`, `± a*x` and `x/d`
+    //! only":
+    //!
+    //! ```compile_fail
+    //! # #[macro_use] extern crate axpy;
+    //! # fn main() {
+    //! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+    //! # let mut z = [0f64; 4];
+    //! # let a = 2f64;
+    //! axpy![rug: z = abs(x)];
+    //! # }
+    //! ```
+}
+
+#[cfg(not(feature = "rug"))]
+mod no_rug {
+    //! The `rug:` prefix, without the `rug` feature.
+    //!
+    //! `rug:` without the feature, reported as "rug: requires the `rug` feature":
+    //!
+    //! ```compile_fail
+    //! # #[macro_use] extern crate axpy;
+    //! # fn main() {
+    //! # let (x, y, w) = ([1f64; 4], [2f64; 4], [3f64; 4]);
+    //! # let mut z = [0f64; 4];
+    //! # let a = 2f64;
+    //! axpy![rug: z = x];
+    //! # }
+    //! ```
+}
@@ -0,0 +1,59 @@
+//! Element types for `axpy![complex: ...]`, which lowers complex combinations to real arithmetic.
+//!
+//! Complex operator overloads tend to defeat the auto-vectorizer. With the `complex:` prefix,
+//! slices of complex numbers are combined in plain real arithmetic instead:
+//! `axpy![complex: z = a*x + y]` computes the real and imaginary part of every element explicitly.
+//! Coefficients may be complex or real.
+
+/// A complex number stored as its real part followed by its imaginary part.
+///
+/// `axpy![complex: z = a*x + y]` reinterprets slices of such values as slices of `[Real; 2]` and
+/// spells out the complex products in real arithmetic, which (unlike the operator overloads of
+/// most complex types) the auto-vectorizer handles well. `[T; 2]` implements it out of the box;
+/// for e.g. `num_complex::Complex<f64>`, which is `#[repr(C)]`,
+///     unsafe impl axpy::complex::Complex for Complex64 {
+///         type Real = f64;
+///         fn parts(self) -> [f64; 2] { [self.re, self.im] }
+///     }
+///
+/// # Safety
+/// `Self` must have the size, alignment and layout of `[Self::Real; 2]`, real part first.
+pub unsafe trait Complex: Copy {
+    type Real: Copy;
+    /// The real and imaginary parts.
+    fn parts(self) -> [Self::Real; 2];
+}
+
+unsafe impl<T: Copy> Complex for [T; 2] {
+    type Real = T;
+    #[inline(always)]
+    fn parts(self) -> [T; 2] {
+        self
+    }
+}
+
+/// A coefficient of a complex combination: a complex number, or a real one.
+pub trait Coef<R> {
+    fn parts(self) -> [R; 2];
+}
+
+impl<C: Complex> Coef<C::Real> for C {
+    #[inline(always)]
+    fn parts(self) -> [C::Real; 2] {
+        Complex::parts(self)
+    }
+}
+
+impl Coef<f32> for f32 {
+    #[inline(always)]
+    fn parts(self) -> [f32; 2] {
+        [self, 0.]
+    }
+}
+
+impl Coef<f64> for f64 {
+    #[inline(always)]
+    fn parts(self) -> [f64; 2] {
+        [self, 0.]
+    }
+}
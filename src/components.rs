@@ -0,0 +1,12 @@
+/// A small vector made of nothing but its components of type `S`, so that a slice of them can be
+/// viewed as the flat slice of their components (see `axpy![components(S): ...]`).
+///
+/// `[T; N]` implements it out of the box; for e.g. `glam::Vec3`, which is `#[repr(C)]`,
+///     unsafe impl axpy::Components<f32> for Vec3 {}
+///
+/// # Safety
+/// `Self` must be `#[repr(C)]` (or `#[repr(transparent)]`) with fields of type `S` only, and no
+/// padding: its size a multiple of that of `S`, every byte of it part of a component.
+pub unsafe trait Components<S> {}
+
+unsafe impl<T, const N: usize> Components<T> for [T; N] {}
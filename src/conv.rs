@@ -0,0 +1,22 @@
+/// Convolution with a short kernel of fixed length, e.g. an FIR filter or a smoothing window.
+///
+/// `conv![y = k * x]`, with `*` standing for the convolution and `k` an array `[T; N]` (or an
+/// array literal, `conv![y = [0.25, 0.5, 0.25] * x]`), computes the "valid" part
+///     y[i] = k[0]*x[i + N - 1] + k[1]*x[i + N - 2] + ... + k[N - 1]*x[i]
+///
+/// for the `x.len() - N + 1` windows of `x` that fit (or as many as fit in `y`), which leaves the
+/// rest of `y` unchanged. The length of the kernel is a constant, so the sum over the taps in each
+/// window is fully unrolled. Any assignment operator may be used, e.g. `y += k * x`.
+#[macro_export]
+macro_rules! conv {
+    [$y:ident $assign:tt $k:tt * $x:ident] => {
+        {
+            let taps = &$k;
+            #[allow(clippy::assign_op_pattern)]
+            for (car, w) in $y.iter_mut().zip($x.windows(taps.len())) {
+                *car $assign $crate::__rt::fir(taps, w);
+            }
+        }
+    };
+    [$($rest:tt)*] => { compile_error!(concat!("conv!: expected `y = k * x`, found `", stringify!($($rest)*), "`")) };
+}
@@ -0,0 +1,152 @@
+//! cuBLAS calls for `axpy![cuda(h): ...]`, on vectors in GPU memory.
+//!
+//! A statement `y = a*x + b*w + c*v` becomes `geam(a, x, b, w, y)`, which writes the first two
+//! terms in one pass, and `axpy(c, v, y)` for each further term, and `y += ...` (or `-=`) one
+//! `axpy` per term. A statement of `n` terms thus takes `n - 1` passes over the device memory
+//! (`n` when accumulating, a single term `=` being `copy` and `scal`). No kernel is generated for
+//! the multi-term forms: the single pass of a fused kernel would need NVRTC or prebuilt PTX,
+//! which this crate does not take on, so the cuBLAS calls are what `cuda(h):` provides.
+//!
+//! The crate declares the cuBLAS functions but does not link them: a build script of the program
+//! does, e.g. with `cargo:rustc-link-lib=cublas`, after which the handle and the buffers come from
+//! the CUDA runtime as usual (`cublasCreate_v2`, `cudaMalloc`, ...).
+
+use core::convert::TryFrom;
+use core::ffi::{c_int, c_void};
+
+/// A cuBLAS context, i.e. a `cublasHandle_t`.
+#[derive(Clone, Copy, Debug)]
+pub struct Handle(pub *mut c_void);
+
+/// A failed cuBLAS call's `cublasStatus_t`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Status(pub c_int);
+
+/// A vector of `len` elements in GPU memory.
+#[derive(Debug)]
+pub struct Dev<T> {
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T> Dev<T> {
+    /// The `len` elements at `ptr`.
+    ///
+    /// # Safety
+    /// `ptr` is device memory (as returned by `cudaMalloc`) that stays allocated, and is not
+    /// otherwise accessed, for as long as the `Dev` is used.
+    pub unsafe fn from_raw(ptr: *mut T, len: usize) -> Dev<T> {
+        Dev { ptr, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    // (taken by method call in the expansion, so that owned vectors and references to them both
+    // work, as slices do with `&mut y[..]`)
+    #[doc(hidden)]
+    pub fn dev(&self) -> &Dev<T> {
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn dev_mut(&mut self) -> &mut Dev<T> {
+        self
+    }
+}
+
+#[allow(non_snake_case)]
+extern "C" {
+    fn cublasSaxpy_v2(h: *mut c_void, n: c_int, a: *const f32, x: *const f32, incx: c_int, y: *mut f32, incy: c_int) -> c_int;
+    fn cublasDaxpy_v2(h: *mut c_void, n: c_int, a: *const f64, x: *const f64, incx: c_int, y: *mut f64, incy: c_int) -> c_int;
+    fn cublasSscal_v2(h: *mut c_void, n: c_int, a: *const f32, x: *mut f32, incx: c_int) -> c_int;
+    fn cublasDscal_v2(h: *mut c_void, n: c_int, a: *const f64, x: *mut f64, incx: c_int) -> c_int;
+    fn cublasScopy_v2(h: *mut c_void, n: c_int, x: *const f32, incx: c_int, y: *mut f32, incy: c_int) -> c_int;
+    fn cublasDcopy_v2(h: *mut c_void, n: c_int, x: *const f64, incx: c_int, y: *mut f64, incy: c_int) -> c_int;
+    fn cublasSgeam(h: *mut c_void, ta: c_int, tb: c_int, m: c_int, n: c_int, a: *const f32, x: *const f32, ldx: c_int,
+                   b: *const f32, w: *const f32, ldw: c_int, y: *mut f32, ldy: c_int) -> c_int;
+    fn cublasDgeam(h: *mut c_void, ta: c_int, tb: c_int, m: c_int, n: c_int, a: *const f64, x: *const f64, ldx: c_int,
+                   b: *const f64, w: *const f64, ldw: c_int, y: *mut f64, ldy: c_int) -> c_int;
+}
+
+/// An element type with cuBLAS routines, i.e. `f32` or `f64`.
+///
+/// # Safety
+/// The routines are the raw cuBLAS calls: the handle is valid, and the pointers are device memory
+/// of at least `n` elements.
+#[allow(clippy::missing_safety_doc)]
+pub trait Cublas: Copy {
+    /// `y += a*x` over `n` elements.
+    unsafe fn axpy(h: Handle, n: c_int, a: Self, x: *const Self, y: *mut Self) -> c_int;
+    /// `x = a*x` over `n` elements.
+    unsafe fn scal(h: Handle, n: c_int, a: Self, x: *mut Self) -> c_int;
+    /// `y = x` over `n` elements.
+    unsafe fn copy(h: Handle, n: c_int, x: *const Self, y: *mut Self) -> c_int;
+    /// `y = a*x + b*w` over `n` elements.
+    unsafe fn geam(h: Handle, n: c_int, a: Self, x: *const Self, b: Self, w: *const Self, y: *mut Self) -> c_int;
+}
+
+macro_rules! cublas {
+    ($t:ident, $axpy:ident, $scal:ident, $copy:ident, $geam:ident) => {
+        impl Cublas for $t {
+            unsafe fn axpy(h: Handle, n: c_int, a: $t, x: *const $t, y: *mut $t) -> c_int {
+                $axpy(h.0, n, &a, x, 1, y, 1)
+            }
+            unsafe fn scal(h: Handle, n: c_int, a: $t, x: *mut $t) -> c_int {
+                $scal(h.0, n, &a, x, 1)
+            }
+            unsafe fn copy(h: Handle, n: c_int, x: *const $t, y: *mut $t) -> c_int {
+                $copy(h.0, n, x, 1, y, 1)
+            }
+            // (the vectors as n-by-1 matrices, untransposed)
+            unsafe fn geam(h: Handle, n: c_int, a: $t, x: *const $t, b: $t, w: *const $t, y: *mut $t) -> c_int {
+                let ld = n.max(1);
+                $geam(h.0, 0, 0, n, 1, &a, x, ld, &b, w, ld, y, ld)
+            }
+        }
+    };
+}
+
+cublas!(f32, cublasSaxpy_v2, cublasSscal_v2, cublasScopy_v2, cublasSgeam);
+cublas!(f64, cublasDaxpy_v2, cublasDscal_v2, cublasDcopy_v2, cublasDgeam);
+
+fn check(status: c_int) -> Result<(), Status> {
+    if status == 0 { Ok(()) } else { Err(Status(status)) }
+}
+
+/// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]`, or `y += ...` if `accumulate`, over the shortest length.
+pub fn combine<T: Cublas, const N: usize>(h: &Handle, y: &mut Dev<T>, accumulate: bool, a: [T; N], x: [&Dev<T>; N])
+    -> Result<(), Status>
+{
+    let n = x.iter().fold(y.len(), |n, x| n.min(x.len()));
+    let n = c_int::try_from(n).expect("axpy!: cuda: vector too long for cuBLAS");
+    // the Dev constructors vouch for the pointers
+    unsafe {
+        let mut k = 0;
+        if !accumulate && N == 1 {
+            check(T::copy(*h, n, x[0].as_ptr(), y.as_mut_ptr()))?;
+            check(T::scal(*h, n, a[0], y.as_mut_ptr()))?;
+            k = 1;
+        } else if !accumulate && N > 1 {
+            check(T::geam(*h, n, a[0], x[0].as_ptr(), a[1], x[1].as_ptr(), y.as_mut_ptr()))?;
+            k = 2;
+        }
+        for k in k..N {
+            check(T::axpy(*h, n, a[k], x[k].as_ptr(), y.as_mut_ptr()))?;
+        }
+    }
+    Ok(())
+}
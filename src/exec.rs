@@ -0,0 +1,144 @@
+//! Executors for `par(e):` statements, which split the target into one contiguous part per job
+//! and evaluate the parts concurrently.
+//!
+//! The crate runs no threads of its own: `Sequential` runs the jobs in turn, `Threads` (with the
+//! `std` feature) on scoped threads, and any other pool or job system can be used by implementing
+//! `Executor`, e.g. for rayon
+//!     struct Rayon;
+//!     unsafe impl Executor for Rayon {
+//!         fn jobs(&self) -> usize { rayon::current_num_threads() }
+//!         fn run<F: Fn(usize) + Sync>(&self, n: usize, job: F) { (0..n).into_par_iter().for_each(job) }
+//!     }
+//!
+//! after which `axpy![par(Rayon): z = a*x + y]` is evaluated on rayon's workers.
+//!
+//! Threads can also be managed by hand with `partition!`, which splits the target and the source
+//! operands alike into disjoint parts, e.g.
+//!     std::thread::scope(|scope| {
+//!         for (z, x, y) in partition!(4, 16; z, x, y) {
+//!             scope.spawn(move || axpy![z = a*x + y]);
+//!         }
+//!     });
+//!
+//! where every part of `z` but the last has a multiple of 16 elements, and `x` and `y` are cut at
+//! the same indices (a part of a shorter source may be shorter, or empty).
+
+/// The target `y` and the sources `x, ...`, each split into `n` parts (or fewer, if there are
+/// fewer elements) at the same indices, with every part of `y` but the last a multiple of `align`
+/// elements long (1 if not given), as an iterator of tuples `(y, x, ...)` of slices.
+#[macro_export]
+macro_rules! partition {
+    ($n:expr; $y:ident $(, $x:ident)*) => { partition!($n, 1; $y $(, $x)*) };
+    ($n:expr, $align:expr; $y:ident $(, $x:ident)*) => {
+        {
+            let size = $crate::exec::part_size($y.len(), $n, $align);
+            $( let $x = &$x[..]; )*
+            $y[..].chunks_mut(size).enumerate().map(move |(k, $y)| {
+                let (s, e) = (k * size, k * size + $y.len());
+                ($y, $($crate::__rt::tile($x, s, e)),*)
+            })
+        }
+    };
+}
+
+use core::marker::PhantomData;
+
+/// A way of running jobs, possibly concurrently.
+///
+/// # Safety
+/// `run(n, job)` calls `job(k)` at most once for every `k` in `0..n` (every call is given a
+/// distinct part of the target), and returns only after all of the calls have returned.
+pub unsafe trait Executor {
+    /// The number of jobs worth splitting the work into, e.g. the number of workers.
+    fn jobs(&self) -> usize;
+    /// Calls `job(k)` for every `k` in `0..n`, in any order and on any threads.
+    fn run<F: Fn(usize) + Sync>(&self, n: usize, job: F);
+}
+
+unsafe impl<E: Executor + ?Sized> Executor for &E {
+    fn jobs(&self) -> usize {
+        (**self).jobs()
+    }
+    fn run<F: Fn(usize) + Sync>(&self, n: usize, job: F) {
+        (**self).run(n, job)
+    }
+}
+
+/// Runs the jobs one after the other on the calling thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sequential;
+
+unsafe impl Executor for Sequential {
+    fn jobs(&self) -> usize {
+        1
+    }
+    fn run<F: Fn(usize) + Sync>(&self, n: usize, job: F) {
+        (0..n).for_each(job)
+    }
+}
+
+/// Runs every job on a scoped thread of its own, in as many jobs as there are available threads.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Threads;
+
+#[cfg(feature = "std")]
+unsafe impl Executor for Threads {
+    fn jobs(&self) -> usize {
+        std::thread::available_parallelism().map_or(1, |t| t.get())
+    }
+    fn run<F: Fn(usize) + Sync>(&self, n: usize, job: F) {
+        let job = &job;
+        std::thread::scope(|scope| {
+            for k in 0..n {
+                scope.spawn(move || job(k));
+            }
+        });
+    }
+}
+
+/// The length of the parts of `len` elements split into `jobs` parts of whole rows of `row`
+/// elements (the last part possibly shorter).
+#[doc(hidden)]
+pub fn part_size(len: usize, jobs: usize, row: usize) -> usize {
+    let row = row.max(1);
+    len.div_ceil(row).div_ceil(jobs.max(1)).max(1) * row
+}
+
+/// A mutable slice split into parts of `size` elements (the last possibly shorter), which jobs
+/// take by index.
+#[doc(hidden)]
+pub struct Parts<'a, T: 'a> {
+    ptr: *mut T,
+    len: usize,
+    size: usize,
+    _slice: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<'a, T: Send> Sync for Parts<'a, T> {}
+
+impl<'a, T> Parts<'a, T> {
+    /// `x` in parts of `size` elements, for a `size` of `len`/`jobs` rounded up to whole rows of
+    /// `row` elements.
+    pub fn new(x: &'a mut [T], jobs: usize, row: usize) -> Parts<'a, T> {
+        let size = part_size(x.len(), jobs, row);
+        Parts { ptr: x.as_mut_ptr(), len: x.len(), size, _slice: PhantomData }
+    }
+
+    /// The number of parts.
+    pub fn count(&self) -> usize {
+        self.len.div_ceil(self.size)
+    }
+
+    /// The offset of part `k` in the slice, and the part.
+    ///
+    /// # Safety
+    /// Every part is taken at most once.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn part(&self, k: usize) -> (usize, &'a mut [T]) {
+        let s = k * self.size;
+        assert!(s < self.len, "axpy!: par: no part {}", k);
+        let n = self.size.min(self.len - s);
+        (s, core::slice::from_raw_parts_mut(self.ptr.add(s), n))
+    }
+}
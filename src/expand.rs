@@ -0,0 +1,23 @@
+/// Evaluates an `axpy!` statement and returns the code it was lowered to, as a `&'static str`.
+///
+/// `let code = axpy_expand![z = a*x + y];` performs the update exactly as `axpy!` would, while
+/// `code` holds the generated loop (as produced by `stringify!`, so spacing is the compiler's),
+///     for (car, (s0, s1)) in z.iter_mut().zip(x.iter().zip(y.iter())) {
+///         *car = $crate::__rt::Scale($crate::__rt::Term(a, (*s0))).get() + (*s1);
+///     }
+///
+/// where `Scale(Term(a, x)).get()` is `a * x` (it only differs for `Wrapping` and `Saturating`).
+///
+/// This is meant for checking what a statement becomes, e.g. which operands end up zipped, without
+/// reaching for `cargo expand` or `trace_macros!`. The slice and `ptr(n):` forms are supported.
+#[macro_export]
+macro_rules! axpy_expand {
+    [ptr($n:expr): $y:ident [$i:ident] $assign:tt $($rest:tt)+] => {
+        { let n: usize = $n; axpy![! ((expand $i) (ptr n) $y $assign ()) () $($rest)* + .] }
+    };
+    [ptr($n:expr): $y:ident $assign:tt $($rest:tt)+] => {
+        { let n: usize = $n; axpy![! (expand (ptr n) $y $assign ()) () $($rest)* + .] }
+    };
+    [$y:ident [$i:ident] $assign:tt $($rest:tt)+] => { axpy![! ((expand $i) () $y $assign ()) () $($rest)* + .] };
+    [$y:ident $assign:tt $($rest:tt)+] => { axpy![! (expand () $y $assign ()) () $($rest)* + .] };
+}
@@ -0,0 +1,174 @@
+//! Linear combinations written with operators instead of a macro, for code that avoids macros.
+//!
+//! Wrapping the slices in `V` makes the usual operators build an expression tree rather than
+//! compute anything, and `assign` then evaluates the tree in a single fused loop, e.g.
+//!     use axpy::expr::{Assign, V, Y};
+//!     z.assign(2.0*V(&x) + V(&y) - Y);  // axpy![z = 2.0*x + y - z]
+//!
+//! where `Y` stands for the target itself (which cannot also be borrowed as an operand). The tree
+//! is a nest of small generic structs, so after inlining the loop is the same as `axpy!`'s.
+//! Coefficients are written before their operand, and must be of a primitive numeric type.
+
+use core::cmp::min;
+use core::ops;
+
+/// An expression tree, evaluated element by element.
+pub trait Expr<T> {
+    /// The number of elements of the shortest operand (`usize::MAX` if there are none).
+    fn count(&self) -> usize;
+    /// Element `i`, given the current value `y` of the target's.
+    fn at(&self, i: usize, y: T) -> T;
+}
+
+/// A slice operand.
+#[derive(Clone, Copy, Debug)]
+pub struct V<'a, T: 'a>(pub &'a [T]);
+
+/// The target of the assignment, as an operand.
+#[derive(Clone, Copy, Debug)]
+pub struct Y;
+
+/// An expression multiplied by a coefficient.
+#[derive(Clone, Copy, Debug)]
+pub struct Scaled<A, E>(pub A, pub E);
+
+/// The sum of two expressions.
+#[derive(Clone, Copy, Debug)]
+pub struct Sum<E, F>(pub E, pub F);
+
+/// The difference of two expressions.
+#[derive(Clone, Copy, Debug)]
+pub struct Difference<E, F>(pub E, pub F);
+
+/// A negated expression.
+#[derive(Clone, Copy, Debug)]
+pub struct Negated<E>(pub E);
+
+impl<'a, T: Copy> Expr<T> for V<'a, T> {
+    #[inline(always)]
+    fn count(&self) -> usize {
+        self.0.len()
+    }
+    #[inline(always)]
+    fn at(&self, i: usize, _: T) -> T {
+        self.0[i]
+    }
+}
+
+impl<T> Expr<T> for Y {
+    #[inline(always)]
+    fn count(&self) -> usize {
+        usize::MAX
+    }
+    #[inline(always)]
+    fn at(&self, _: usize, y: T) -> T {
+        y
+    }
+}
+
+impl<T: Copy + ops::Mul<Output = T>, E: Expr<T>> Expr<T> for Scaled<T, E> {
+    #[inline(always)]
+    fn count(&self) -> usize {
+        self.1.count()
+    }
+    #[inline(always)]
+    fn at(&self, i: usize, y: T) -> T {
+        self.0 * self.1.at(i, y)
+    }
+}
+
+impl<T: Copy + ops::Add<Output = T>, E: Expr<T>, F: Expr<T>> Expr<T> for Sum<E, F> {
+    #[inline(always)]
+    fn count(&self) -> usize {
+        min(self.0.count(), self.1.count())
+    }
+    #[inline(always)]
+    fn at(&self, i: usize, y: T) -> T {
+        self.0.at(i, y) + self.1.at(i, y)
+    }
+}
+
+impl<T: Copy + ops::Sub<Output = T>, E: Expr<T>, F: Expr<T>> Expr<T> for Difference<E, F> {
+    #[inline(always)]
+    fn count(&self) -> usize {
+        min(self.0.count(), self.1.count())
+    }
+    #[inline(always)]
+    fn at(&self, i: usize, y: T) -> T {
+        self.0.at(i, y) - self.1.at(i, y)
+    }
+}
+
+impl<T: ops::Neg<Output = T>, E: Expr<T>> Expr<T> for Negated<E> {
+    #[inline(always)]
+    fn count(&self) -> usize {
+        self.0.count()
+    }
+    #[inline(always)]
+    fn at(&self, i: usize, y: T) -> T {
+        -self.0.at(i, y)
+    }
+}
+
+/// Evaluation of an expression tree into a slice.
+pub trait Assign<T> {
+    /// `self = e`, over the shortest length, as `axpy!` would.
+    fn assign<E: Expr<T>>(&mut self, e: E);
+}
+
+impl<T: Copy> Assign<T> for [T] {
+    #[inline]
+    fn assign<E: Expr<T>>(&mut self, e: E) {
+        let n = min(self.len(), e.count());
+        for (i, y) in self[..n].iter_mut().enumerate() {
+            *y = e.at(i, *y);
+        }
+    }
+}
+
+// `+`, `-` and unary `-` build the tree from any of its nodes, ...
+macro_rules! node {
+    ($t:ident [$($p:tt),*]) => {
+        impl<$($p,)* R> ops::Add<R> for $t<$($p),*> {
+            type Output = Sum<Self, R>;
+            #[inline(always)]
+            fn add(self, r: R) -> Sum<Self, R> {
+                Sum(self, r)
+            }
+        }
+        impl<$($p,)* R> ops::Sub<R> for $t<$($p),*> {
+            type Output = Difference<Self, R>;
+            #[inline(always)]
+            fn sub(self, r: R) -> Difference<Self, R> {
+                Difference(self, r)
+            }
+        }
+        impl<$($p),*> ops::Neg for $t<$($p),*> {
+            type Output = Negated<Self>;
+            #[inline(always)]
+            fn neg(self) -> Negated<Self> {
+                Negated(self)
+            }
+        }
+        node!(@scale $t [$($p),*] f32 f64 i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+    };
+    // ... and a coefficient of a primitive type scales any node
+    (@scale $t:ident [$($p:tt),*] $a:ident $($rest:ident)*) => {
+        impl<$($p),*> ops::Mul<$t<$($p),*>> for $a {
+            type Output = Scaled<$a, $t<$($p),*>>;
+            #[inline(always)]
+            fn mul(self, e: $t<$($p),*>) -> Scaled<$a, $t<$($p),*>> {
+                Scaled(self, e)
+            }
+        }
+        node!(@scale $t [$($p),*] $($rest)*);
+    };
+    (@scale $t:ident $p:tt) => {};
+}
+
+node!(V ['a, T]);
+node!(Y []);
+node!(Scaled [A, E]);
+node!(Sum [E, F]);
+node!(Difference [E, F]);
+node!(Negated [E]);
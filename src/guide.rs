@@ -0,0 +1,299 @@
+//! The forms of `axpy!` statements in detail, for the features that have no module of their own.
+//!
+//! # Targets and assignments
+//!
+//! In addition to `=`, both `+=` and `-=` are supported, as well as the elementwise `.*=` and `./=`
+//! for scaling the target in place, e.g. `axpy![z .*= window]`. (Technically *any* assignment
+//! operator works, but that is an accident of implementation rather than an intended feature.)
+//! A statement evaluates to `()`; prefixed with `&mut` it instead evaluates to the updated target
+//! as a `&mut [T]`, so that it can be passed on directly, as in `solve(axpy![&mut r = b - q])`.
+//!
+//! The target may also be a field or an index expression, e.g. `self.residual`, `&mut scratch[a..b]`
+//! or `(buffers[k])` (parenthesized, as `buffers[k]` with an identifier `k` names the loop index, see
+//! below). It is borrowed once, and where it recurs on the right-hand side, as in
+//! `self.r = x - 0.5*self.r`, that borrow is used.
+//!
+//! The assigned variable is only ever borrowed once, through `iter_mut()`, no matter how often it
+//! appears. An axpby-style update `axpy![y = b*y + a*x]` is therefore guaranteed to be a single
+//! read-modify-write pass over `y`:
+//!     for (y, x) in y.iter_mut().zip(x.iter()) {
+//!         *y = b * *y + a * *x;
+//!     }
+//! Likewise, a source appearing in several terms, as in `z = x + 2.0*x + y`, is zipped only once and
+//! each of its terms reads the same element. The terms are not merged into `3.0*x`, which could
+//! round differently.
+//!
+//! Naming the index of the target, `z[i] = x + (i as f64)*y`, binds `i: usize` to the position of
+//! the current element, for use in coefficients (the target is then iterated with `enumerate()`).
+//!
+//! The values of a right-hand side can also be pushed into a sink rather than stored in a target:
+//! `axpy![extend(s): a*x + y]` adds them to any `s: Extend<T>` (a `Vec`, a serializer's buffer,
+//! ...) as one iterator, and `axpy![each(f): a*x + y]` calls `f(i, value)` for every index `i`,
+//! e.g. to downsample on the fly, without an intermediate buffer either way.
+//!
+//! Long combinations, e.g. of a few dozen basis vectors, need no special treatment: a term `± a*x`
+//! or `± x` costs about three steps of macro recursion, so some 35 of them fit within the default
+//! `recursion_limit` of 128 (and a crate combining more can raise it, `#![recursion_limit = "256"]`).
+//! The sources are zipped as a balanced tree, which keeps the nesting shallow as well.
+//!
+//! The common slips, a missing `*`, a dangling trailing operator, or the assigned variable used as
+//! a coefficient, are reported as such rather than as a failure deep inside the expansion.
+//!
+//! # Coefficients and element types
+//!
+//! Coefficients may be compatible scalar literals or variables, and may also be written after the
+//! operand when they are literals or parenthesized, `z = x*2.0 + y*(b)` (in `y*b`, `b` is taken to
+//! be the vector). Slices of `Wrapping<T>` or `Saturating<T>` take coefficients of the inner type
+//! `T` as well, e.g. `a: i32` in `y = a*x + y` for `x, y: &[Wrapping<i32>]`.
+//!
+//! A term may be divided by a scalar, `z = x / h`, and so may a parenthesized combination,
+//! `z = (x - y) / h`, which is distributed over its terms. `f32` and `f64` elements are multiplied
+//! by the reciprocal, computed once outside the loop (so the result may differ from a true division
+//! in the last bit); other types, integers in particular, are divided.
+//!
+//! Coefficient and element types need not agree either, only the products and sums have to: with
+//! `uom` quantities, `position += dt*velocity` multiplies `Velocity` elements by a `Time` and
+//! accumulates the resulting `Length`s. Operands of another primitive type are converted with
+//! `as`, so that `axpy![z = x as f32 + a*y]` writes the `f64` slice `x` into the `f32` slice `z`
+//! without a separate conversion pass.
+//!
+//! Types whose operators panic on overflow, such as `rust_decimal::Decimal`, work as they are. With
+//! the `checked_ops:` prefix every operation goes through the type's `checked_add`, `checked_sub` and
+//! `checked_mul` methods instead (integers and `Decimal` have them), a negated leading term through
+//! `checked_neg`, and
+//! `axpy![checked_ops: z = a*x + y]` evaluates to a `Result<(), Overflow>` reporting the first
+//! element that overflowed.
+//!
+//! With the `rug` feature, slices of arbitrary-precision floats, `rug::Float`, are combined by
+//! statements prefixed with `rug:`, e.g. `axpy![rug: y = a*x + y]`, in the same syntax as for `f64`.
+//! A `Float` allocates its mantissa, so rather than building a temporary for every element, the
+//! statement accumulates into one value, `acc += &x`, scales a copy into another, `t.clone_from(&x);
+//! t *= a`, and swaps the accumulated value into the target, these two values and their
+//! allocations being reused from element to element. The terms are `± x`, `± a*x` and `x/d`, with
+//! the coefficients taken by value (a `Float` coefficient is written `(&a)`), and the target's
+//! elements are taken to share one precision. (The expansion only uses `clone_from` and the
+//! assigning operators, so the crate does not depend on `rug`.)
+//!
+//! # Operands
+//!
+//! An operand may be anything with `iter()` (and the target anything with `iter_mut()`), either as
+//! inherent methods or through the `AxpySource` and `AxpyTarget` traits. It may also be a macro
+//! invocation that yields a slice, `z = x + view!(buf, 3)`, which is evaluated once per occurrence.
+//!
+//! An operand may also be a function of a vector, e.g. `z = a*sqrt(x) + abs(y)`, which is lowered
+//! to a method call on each element, `a * (*x).sqrt() + (*y).abs()`. Any method of the element type
+//! taking no arguments can be used this way; note that in `no_std` crates the floating-point types
+//! only provide the subset implemented in `core` (`abs`, `recip`, `signum`, ...), while `sqrt`,
+//! `exp`, `ln` and friends require `std`.
+//!
+//! Computed streams mix in through `iter(f)`, where `f` is any iterator over values of the element
+//! type (e.g. a generator of boundary values, or `core::iter::repeat(c)`), which is zipped as it
+//! is rather than through `.iter()`, e.g. `z = x + a*iter(f)`. It is consumed by the statement, and
+//! may be combined with a function, `abs(iter(f))`.
+//!
+//! An operand read back to front is written `rev(x)`, the vector `x` reversed (and then, like any
+//! operand, truncated to the shortest one), e.g. `z = x + rev(y)` for a correlation or a
+//! palindromic filter, which is zipped in as `y.iter().rev()` rather than copied. It is supported in
+//! plain statements, without a prefix.
+//!
+//! A rotated operand `rot(x, k)` reads `x[(i + k) % n]` for the `n` elements of `x`, with `k` an
+//! integer of either sign, e.g. `axpy![z = rot(x, 1) - rot(x, -1)]` for a periodic difference or
+//! `axpy![c += a*rot(buf, head)]` for a circular buffer. Every rotation splits the rows at its
+//! wraparound into two segments, each an ordinary fused loop reading a slice of `x`, so nothing is
+//! copied. Rotated operands are supported in plain statements only.
+//!
+//! Finite-difference stencils combine shifted operands, written `x(k)` for the vector of elements
+//! `x[i + k]` with `k` an integer literal, e.g. `axpy![stencil(clamp): z = x(-1) - 2.0*x + x(+1)]`.
+//! The rows at either end, where a shift leaves `x`, follow the policy: `skip` leaves them unchanged,
+//! `clamp` uses the first or last element of `x` instead, and `wrap` wraps around, as for periodic
+//! boundaries. The interior rows are an ordinary fused loop over slices. The target cannot itself
+//! be shifted.
+//!
+//! A sparse vector given by the positions `idx` of its nonzero elements and their values `vals`
+//! (the BLAS `axpyi`) is added to a dense one with `axpy![y += a*sparse(idx, vals)]`, which
+//! touches only those positions of `y`. The indices may be of any integer type, and the
+//! coefficient is optional, as is `-=` for subtracting.
+//!
+//! A column of a row-major matrix stored in a flat slice, i.e. the elements `j`, `j + lda`,
+//! `j + 2*lda`, ..., is written `col(A, j, lda)`, and may be the target or any of the operands,
+//! e.g. `axpy![col(A, j, lda) += a*x]` (the number of rows is that of the shortest operand). A
+//! matrix whose column is assigned cannot otherwise appear in the statement. The same access
+//! pattern is one channel of an interleaved buffer, e.g. of audio frames `LRLR...`, for which
+//! `channel(buf, c, channels)` is another name, e.g.
+//!     axpy![channel(out, 0, 2) = g*channel(inp, 0, 2) + dry]
+//!
+//! A panel of a row-major matrix stored in a flat slice, the `cols` elements at the start of each
+//! of `rows` rows that start `ld` elements apart (the leading dimension), is written
+//! `panel(A, rows, cols, ld)`, e.g. for the block of `C` starting at row `i` and column `j`,
+//!     let c = &mut C[i * ldc + j..];
+//!     axpy![panel(c, mb, nb, ldc) += alpha*panel(a, mb, nb, lda)]
+//! The target must then be a panel, and the statement is evaluated row by row, as an ordinary
+//! fused loop over the rows of the panels, with any other slice operand read as a packed panel
+//! (its rows `cols` elements apart). The smallest number of rows and columns is used.
+//!
+//! # Bounds, extrema and masks
+//!
+//! The right-hand side as a whole may be bounded, `z = clamp(x + a*y, lo, hi)` with scalar bounds,
+//! or be the elementwise extremum of several combinations, e.g. `z = max(x, y - b*w)` (and `min`).
+//! These are plain selects, so they vectorize; a NaN in the first argument passes through. The
+//! magnitude of a combination is `abs(x - y)`.
+//!
+//! A comparison of a single operand or a parenthesized combination writes a mask, a slice of
+//! `bool`, e.g. `axpy![m = (x - y) > tol]`. The right side is a scalar if it is a single token (a
+//! variable, literal or parenthesized expression, optionally negated), as `tol` here, and otherwise
+//! another combination, e.g. `m = x <= 2.0*y`. Any of `<`, `<=`, `>`, `>=`, `==` and `!=` may be
+//! used, and the comparison is branch-free like the selects above.
+//!
+//! # Lengths
+//!
+//! Operands of unequal length are by default truncated to the shortest, as `zip` does. The prefix
+//! `strict:` instead panics naming the offending operand and both lengths, `checked:` makes the
+//! statement return `Result<(), LengthMismatch>` (evaluating it only if all lengths agree), and
+//! `truncate:` asks for the default explicitly. The `strict` feature makes `strict:` the default.
+//! `exact:` panics like `strict:`, and then evaluates the statement on operands cut to the target's
+//! length, so that the loop shares one length and is free of per-element bounds checks even when
+//! the operands are not plain slices (all of them must then be sliceable).
+//!
+//! Alternatively, the length can be given up front, `axpy![n; z = a*x + y]` (with `n` a variable, a
+//! literal or a parenthesized expression), which cuts every operand to its first `n` elements
+//! before the loop, panicking if one is shorter. All operands then share the one length, which
+//! helps the bounds checks go away when they come from different kinds of containers.
+//!
+//! # Other containers
+//!
+//! N-dimensional arrays such as `ndarray`'s `Array2`, `ArrayD` and their views are combined by
+//! statements prefixed with `nd:`, e.g. `axpy![nd: z = a*x + y]` for arrays of the same shape.
+//! When they are all contiguous, in the same memory order (standard or Fortran), the statement
+//! runs over their flat memory-order slices, and otherwise it falls back to their element
+//! iterators. (The expansion only calls the methods `shape`, `strides`, `as_slice_memory_order`
+//! and `as_slice_memory_order_mut`, so the crate does not depend on `ndarray`.)
+//!
+//! Slices of small vectors such as `glam::Vec3` or `vek::Vec4<f32>` are operands like any other,
+//! given the operators of the vector type (`f32 * Vec3`, `Vec3 + Vec3`, ...), so that e.g.
+//! `axpy![positions += dt * velocities]` is the usual particle update. Its loop then works on one
+//! vector at a time, though; the prefix `components(f32):` instead views every slice as the flat
+//! slice of its `f32` components, which vectorizes like any slice of `f32`. The vector type must
+//! consist of nothing but its components (`#[repr(C)]`, as glam's and vek's are, with no padding),
+//! which is promised by implementing the unsafe trait `Components`, e.g.
+//!     unsafe impl axpy::Components<f32> for Vec3 {}
+//!     axpy![components(f32): positions += dt * velocities]
+//!
+//! Columns of `faer` matrices, `ColRef` and `ColMut` (e.g. `a.col(j)` and `b.col_mut(j)`), are
+//! combined by statements prefixed with `faer:`, e.g. `axpy![faer: y = a*x + y]`, which run over
+//! their slices if they all have unit stride, or else index them element by element. (As with
+//! `nd:`, the expansion only calls the methods `rb`, `rb_mut`, `try_as_slice`, `try_as_slice_mut`
+//! and `nrows`, and indexes, so there is no dependency on `faer`.)
+//!
+//! Matrices stored as slices of rows (e.g. `Vec<Vec<T>>` or `&mut [&mut [T]]`) are handled by the
+//! `for rows:` prefix, which loops over the rows of every operand in lockstep and evaluates the
+//! statement on each, e.g. `axpy![for rows: Z = X + 2.0*Y]` becomes
+//!     for (Z, (X, (Y,))) in Z.iter_mut().zip(X.iter().zip(Y.iter().map(|x| (x,)))) {
+//!         axpy![Z = X + 2.0*Y];
+//!     }
+//!
+//! The same update applied to many small independent systems, stored back to back in flat buffers,
+//! is `axpy![for rows(n): z = a*x + y]`, which evaluates the statement on every chunk of `n`
+//! elements in turn. `par(e) for rows(n):` splits the rows into one contiguous block per job of
+//! the executor `e` (see `exec`), and `par(e):` does the same for a plain statement, in blocks of
+//! elements. With the `std` feature, `par for rows(n):` uses `exec::Threads`, one scoped thread per
+//! available thread. Coefficients are shared by the jobs, so they must be `Sync`.
+//!
+//! Buffers handed over from C as raw pointers can be combined without first fabricating slices:
+//! prefixing the statement with `ptr(n):` treats every operand as a pointer to (at least) `n`
+//! elements. The expansion calls `core::slice::from_raw_parts`, so it must sit inside an `unsafe`
+//! block, and the usual requirements of that function (non-null, aligned, initialized, and no
+//! source overlapping the target unless it *is* the target) are the caller's to uphold, e.g.
+//!     unsafe { axpy![ptr(n): z = a * x + z] }
+//!
+//! The last of these is checked in debug builds: before the loop, the `n` elements of each
+//! source are compared with those of the target, and any overlap panics with the names and
+//! address ranges of both operands.
+//!
+//! # Debugging and hardware buffers
+//!
+//! Hunting down the first NaN of a long pipeline is easier with the `checked_fp:` prefix: in debug
+//! builds, `axpy![checked_fp: z = a*x + y]` looks at every value it writes, and panics at the first
+//! NaN or infinity with its index and the elements of the target and of each slice operand it was
+//! computed from. Release builds evaluate the statement as usual.
+//!
+//! Buffers shared with hardware, such as DMA regions, are accessed with the `volatile:` prefix,
+//! e.g. `axpy![volatile: tx = g*rx + dc]`: every element of the target and of each slice operand
+//! is read exactly once with a volatile load, and every result written with a volatile store, in
+//! order of increasing index, between two compiler fences, so that none of the accesses is elided,
+//! merged or moved past the code around the statement. This rules out vectorization.
+//!
+//! To see what a statement is lowered to, `axpy_expand!` evaluates it like `axpy!` and additionally
+//! returns the generated loop as a string, and `axpy_at!` evaluates a right-hand side at a single
+//! index, `axpy_at![i; a*x + y]`, e.g. to spot-check an element of the target.
+//!
+//! # Chunks, tiles and temporaries
+//!
+//! The vector width can be made explicit with `tail(strategy, w):`, which evaluates the statement
+//! on whole chunks of `w` elements (a constant), and on the remaining elements, fewer than `w`,
+//! according to the strategy: `scalar` as they are, `masked` as one whole chunk padded with
+//! default values (which must be harmless to compute with, unlike an integer divisor of 0), and
+//! `overlap` as the last `w` elements, some of which are thus computed twice, which is allowed
+//! only for `=` statements not reading the target, e.g. `axpy![tail(overlap, 8): z = a*x + y]`.
+//!
+//! Several statements reading the same large vectors can be run in cache-sized tiles with the
+//! `tile(b):` prefix (or `tile:`, for b = 1024), separating the statements by `;`, e.g.
+//! `axpy![tile(4096): y = a*x + y; z = y - w]` performs both statements on the first 4096 elements,
+//! then both on the next 4096, and so on, so that `y` is still in cache when the second statement
+//! reads it. The result is that of the statements run one after the other. Operands must be slices.
+//! With the `std` feature, `tile(auto):` picks the tile size itself: the first few runs of the
+//! statements try a handful of sizes and are timed, and later runs use the fastest.
+//!
+//! Statements can also share temporaries, declared with `let` in a block starting with one, e.g.
+//! one Runge-Kutta step
+//!     axpy!{
+//!         let k2 = y + (0.5*h)*k1;
+//!         y += (h/6.)*k1 + (h/3.)*k2;
+//!     }
+//!
+//! where `k2` is the vector of its elements. The statements are run on the first 64 elements of
+//! every operand, then on the next 64, and so on, and a temporary only ever holds the elements of
+//! the current chunk, on the stack, so that it costs no pass over memory of its own. The result is
+//! that of the statements run one after the other. Operands must be slices, and the elements
+//! `Copy + Default`. The chunk length can be chosen with the prefix `block(w):`, a constant: a
+//! few vector registers' worth, e.g. `axpy!{block(16): let t = ...; ...}` for `f32` with AVX-512,
+//! lets the temporaries live in registers, and `block(1)` evaluates the statements element by
+//! element.
+//!
+//! With the `std` feature, any of these chunked evaluators (`tile`, `par` and blocks with
+//! temporaries) can be given a hook, called after every chunk with its first index, its length
+//! and the time it took, e.g. to feed a metrics pipeline:
+//!     axpy![hook(|s, n, t| metrics.record(s, n, t)): tile(4096): y = a*x + y; z = y - w]
+//!
+//! The hook is a `Fn(usize, usize, Duration)`, and with `par` it is called on the executor's
+//! threads, so it must then be `Sync` too.
+//!
+//! # Architecture kernels
+//!
+//! With the `arch-x86` feature on x86_64, `axpy![x86: z = a*x + b*y]` evaluates sums of scaled
+//! `f32` or `f64` slices with the hand-written AVX-512 or AVX2 kernels of `x86`, whichever the CPU
+//! supports, independently of what the compiler makes of the generic loop. The statement is `=`,
+//! `+=` or `-=`, and the target cannot also be a term (`+=` does that). Likewise, with the
+//! `arch-aarch64` feature on AArch64, `axpy![aarch64: ...]` uses the NEON kernels of `aarch64`, and
+//! with the `arch-riscv64` feature on RISC-V with the vector extension, `axpy![riscv64: ...]` the
+//! RVV kernels of `riscv64`. In WebAssembly built with the `simd128` target feature,
+//! `axpy![wasm32: ...]` uses the `v128` kernels of `wasm32` (no crate feature needed). Statements
+//! without one of these prefixes always use the portable loop, features and target features
+//! notwithstanding, as the macro cannot see whether their elements are `f32` or `f64`. With the
+//! `cuda` feature, `axpy![cuda(h): ...]` runs the same statements on GPU buffers with cuBLAS (see
+//! `cuda`).
+//!
+//! These kernels fuse every multiplication with the following addition, and so may differ in the
+//! last bit from the portable loop. Where bit-identical results across targets matter, as in
+//! regression tests, prefix the statement with `reproducible:`, which drops any of the four
+//! prefixes and evaluates every element in the same documented order: the terms left to right as
+//! written, `a*x[i]` rounded before it is added (Rust never contracts the two), and for `+=` and
+//! `-=` the whole sum then added to or subtracted from `y[i]`. The last elements are evaluated
+//! exactly like the first, so the length does not matter either, e.g.
+//!     axpy![reproducible: x86: z += a*x - b*y]  // z[i] += (a*x[i]) - (b*y[i])
+//!
+//! As cuBLAS makes no such promise, `reproducible: cuda(h):` is rejected rather than run in an
+//! unspecified order. The other prefixes keep the guarantee: `tail(strategy, w):` and `block(w):`
+//! only change which elements are evaluated together, never the expression an element is computed
+//! with, so the result is the same for every strategy and width. Reductions are a different
+//! matter: `dot![lanes(k): x, y]` associates its sum by the lane count, and so is bit-identical
+//! across targets for a given `k`, but not from one `k` to another, nor to the plain `dot!`.
@@ -0,0 +1,21 @@
+/// Evaluates a linear combination of arrays in a const context.
+///
+/// `const Z: [f64; 4] = axpy_const![2.0*X + Y];` takes the same right-hand sides as `axpy!`
+/// (without functions, of operands or of the whole), and becomes
+///     {
+///         let mut z = X;
+///         let mut i = 0;
+///         while i < z.len() {
+///             z[i] = 2.0 * X[i] + Y[i];
+///             i += 1;
+///         }
+///         z
+///     }
+///
+/// i.e. an array of the type of the first operand, filled by an index-based loop, as iterators
+/// cannot be used in const contexts. The other operands must be at least as long, or the constant
+/// fails to evaluate.
+#[macro_export]
+macro_rules! axpy_const {
+    [$($rest:tt)+] => { axpy![! (emit (const) __axpy_const = ()) () $($rest)* + .] };
+}
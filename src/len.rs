@@ -0,0 +1,37 @@
+use core::fmt;
+
+/// An operand whose length differs from the target's, reported by `axpy![checked: ...]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// The name of the operand.
+    pub operand: &'static str,
+    /// Its length.
+    pub len: usize,
+    /// The name of the assigned vector.
+    pub target: &'static str,
+    /// Its length.
+    pub expected: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` has {} elements, but the target `{}` has {}",
+            self.operand, self.len, self.target, self.expected)
+    }
+}
+
+/// The length policy of a statement without a prefix: `truncate:`, or `strict:` with the `strict`
+/// feature.
+#[cfg(not(feature = "strict"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __axpy_len {
+    [$($rest:tt)+] => { axpy![truncate: $($rest)*] };
+}
+
+#[cfg(feature = "strict")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __axpy_len {
+    [$($rest:tt)+] => { axpy![strict: $($rest)*] };
+}
@@ -0,0 +1,118 @@
+/// Simultaneous update of two vectors in a single pass, e.g. a Givens plane rotation.
+///
+/// `rot![(x, y) = (c*x + s*y, -s*x + c*y)]` becomes
+///     for (x_, y_) in x.iter_mut().zip(y.iter_mut()) {
+///         let (x, y) = (*x_, *y_);
+///         *x_ = c*x + s*y;
+///         *y_ = -s*x + c*y;
+///     }
+///
+/// so both right-hand sides see the old values, and each may be any expression in (the elements
+/// of) `x` and `y`. The dedicated form `rot![x, y; c, s]` applies the rotation above.
+#[macro_export]
+macro_rules! rot {
+    [$x:ident, $y:ident; $c:expr, $s:expr] => {
+        {
+            let (c, s) = ($c, $s);
+            rot![($x, $y) = (c * $x + s * $y, c * $y - s * $x)]
+        }
+    };
+    [($x:ident, $y:ident) = ($ex:expr, $ey:expr)] => {
+        for (car, cdr) in $x.iter_mut().zip($y.iter_mut()) {
+            let ($x, $y) = (*car, *cdr);
+            *car = $ex;
+            *cdr = $ey;
+        }
+    };
+}
+
+/// Exchanges the elements of two vectors in a single pass.
+///
+/// `swap![x, y]` is `rot![(x, y) = (y, x)]`, and like `axpy!` accepts the `ptr(n):` prefix for raw
+/// pointer operands (inside an `unsafe` block), whose ranges must not overlap (which is checked in
+/// debug builds).
+#[macro_export]
+macro_rules! swap {
+    [ptr($n:expr): $x:ident, $y:ident] => {
+        {
+            let n: usize = $n;
+            if cfg!(debug_assertions) {
+                $crate::__rt::no_alias(stringify!($x), $crate::__rt::span($x, n),
+                    &[(stringify!($y), $crate::__rt::span($y, n))]);
+            }
+            let $x = $crate::__rt::from_raw_parts_mut($x, n);
+            let $y = $crate::__rt::from_raw_parts_mut($y, n);
+            swap![$x, $y]
+        }
+    };
+    [$x:ident, $y:ident] => { rot![($x, $y) = ($y, $x)] };
+}
+
+/// Copies one vector into another.
+///
+/// `copy![y = x]` is `axpy![truncate: y = x]`, i.e. copies as many elements as fit in `y` whatever
+/// the length policy, and the `ptr(n):` and `for rows:` prefixes carry over. The
+/// source may also be restricted to a range and optionally strided, e.g. `copy![y = x[1..; 2]]`
+/// copies every other element of `x` starting from the second.
+#[macro_export]
+macro_rules! copy {
+    [ptr($n:expr): $y:ident = $x:ident] => { axpy![ptr($n): $y = $x] };
+    [for rows: $y:ident = $x:ident] => { axpy![for rows: $y = $x] };
+    [$y:ident = $x:ident[$r:expr; $step:expr]] => {
+        for (car, cdr) in $y.iter_mut().zip($x[$r].iter().step_by($step)) {
+            *car = *cdr;
+        }
+    };
+    [$y:ident = $x:ident[$r:expr]] => { { let src = &$x[$r]; axpy![truncate: $y = src] } };
+    [$y:ident = $x:ident] => { axpy![truncate: $y = $x] };
+}
+
+/// In-place scaling of a vector by a scalar.
+///
+/// `scal![x *= a]` becomes
+///     for x in x.iter_mut() {
+///         *x *= a;
+///     }
+///
+/// (`/=` is accepted as well), and like `axpy!` takes the `ptr(n):` prefix for raw pointers.
+#[macro_export]
+macro_rules! scal {
+    [ptr($n:expr): $x:ident $op:tt $a:expr] => {
+        { let $x = $crate::__rt::from_raw_parts_mut($x, $n); scal![$x $op $a] }
+    };
+    [$x:ident *= $a:expr] => { { let a = $a; for car in $x.iter_mut() { *car *= a; } } };
+    [$x:ident /= $a:expr] => { { let a = $a; for car in $x.iter_mut() { *car /= a; } } };
+}
+
+/// Linear interpolation between two vectors.
+///
+/// `lerp![z = x, y; t]` becomes
+///     for (z, (x, y)) in z.iter_mut().zip(x.iter().zip(y.iter())) {
+///         *z = *x + t * (*y - *x);
+///     }
+///
+/// with `t` evaluated once. The target may be either endpoint, e.g. `lerp![x = x, y; t]` moves `x`
+/// towards `y` in place.
+#[macro_export]
+macro_rules! lerp {
+    [$z:ident = $x:ident, $y:ident; $t:expr] => {
+        {
+            let t = $t;
+            macro_rules! eval {
+                ($z $z $z) => { let _ = t; };
+                ($z $z $y) => {
+                    for (car, cdr) in $z.iter_mut().zip($y.iter()) { *car = *car + t * (*cdr - *car); }
+                };
+                ($z $x $z) => {
+                    for (car, cdr) in $z.iter_mut().zip($x.iter()) { *car = *cdr + t * (*car - *cdr); }
+                };
+                ($z $x $y) => {
+                    for (car, (x, y)) in $z.iter_mut().zip($x.iter().zip($y.iter())) {
+                        *car = *x + t * (*y - *x);
+                    }
+                };
+            }
+            eval!($z $x $y)
+        }
+    };
+}
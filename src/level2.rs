@@ -0,0 +1,215 @@
+/// Fused matrix-vector product over a row-major matrix buffer.
+///
+/// `gemv![y = alpha * panel(A, m, n, lda) * x + beta * y]` treats `A` as a flat, row-major `&[T]`
+/// holding `m` rows of `n` elements, each starting `lda` elements after the previous one (as for
+/// `panel` operands of `axpy!`), and becomes
+///     for (y, row) in y[..m].iter_mut().zip(A.chunks(lda)) {
+///         *y = alpha * dot![row[..n], x[..n]] + beta * *y;
+///     }
+///
+/// so each row is a single vectorizable reduction. Both `alpha` and the `beta * y` term are
+/// optional (as is `beta` itself), and any assignment operator may be used, e.g.
+/// `y += panel(A, m, n, n) * x`. The second vector of the trailing term must be the assigned one.
+/// The dimensions are checked up front: the statement panics unless `n <= lda`, `A` holds at least
+/// `m * lda` elements, `x` at least `n` and `y` at least `m`.
+#[macro_export]
+macro_rules! gemv {
+    // normalize every accepted shape to (alpha) (A m n lda) x ((beta) y), where an empty group
+    // means "absent"
+    [$y:ident $assign:tt $alpha:tt * panel($a:ident, $m:expr, $n:expr, $ld:expr) * $x:ident + $beta:tt * $y2:ident] =>
+        { gemv![@ $y $assign ($alpha *) ($a ($m) ($n) ($ld)) $x (($beta *) $y2)] };
+    [$y:ident $assign:tt $alpha:tt * panel($a:ident, $m:expr, $n:expr, $ld:expr) * $x:ident + $y2:ident] =>
+        { gemv![@ $y $assign ($alpha *) ($a ($m) ($n) ($ld)) $x (() $y2)] };
+    [$y:ident $assign:tt $alpha:tt * panel($a:ident, $m:expr, $n:expr, $ld:expr) * $x:ident] =>
+        { gemv![@ $y $assign ($alpha *) ($a ($m) ($n) ($ld)) $x ()] };
+    [$y:ident $assign:tt panel($a:ident, $m:expr, $n:expr, $ld:expr) * $x:ident + $beta:tt * $y2:ident] =>
+        { gemv![@ $y $assign () ($a ($m) ($n) ($ld)) $x (($beta *) $y2)] };
+    [$y:ident $assign:tt panel($a:ident, $m:expr, $n:expr, $ld:expr) * $x:ident + $y2:ident] =>
+        { gemv![@ $y $assign () ($a ($m) ($n) ($ld)) $x (() $y2)] };
+    [$y:ident $assign:tt panel($a:ident, $m:expr, $n:expr, $ld:expr) * $x:ident] =>
+        { gemv![@ $y $assign () ($a ($m) ($n) ($ld)) $x ()] };
+
+    // the trailing term may only reference the assigned vector, which we read through `car`
+    [@ $y:ident $assign:tt ($($alpha:tt)*) $a:tt $x:ident (($($beta:tt)*) $y2:ident)] => {
+        {
+            macro_rules! eval {
+                ($y $y) => {};
+                ($y2 $y) => { compile_error!("gemv!: the trailing term must scale the assigned vector") };
+            }
+            eval!($y2 $y);
+            gemv![@rows $y $a $x row car {
+                #[allow(clippy::assign_op_pattern)]
+                { *car $assign $($alpha)* dot![row, $x] + $($beta)* *car; }
+            }]
+        }
+    };
+    [@ $y:ident $assign:tt ($($alpha:tt)*) $a:tt $x:ident ()] => {
+        gemv![@rows $y $a $x row car { *car $assign $($alpha)* dot![row, $x]; }]
+    };
+
+    // check the dimensions, cut x to the row length, and run the body for every row
+    [@rows $y:ident ($a:ident $m:tt $n:tt $ld:tt) $x:ident $row:ident $car:ident $body:block] => {
+        {
+            let (m, n, ld): (usize, usize, usize) = ($m, $n, $ld);
+            assert!(n <= ld && $a.len() >= m.saturating_mul(ld),
+                "gemv!: a matrix of {} rows of {} elements, {} apart, does not fit in {} elements",
+                m, n, ld, $a.len());
+            assert!($x.len() >= n && $y.len() >= m,
+                "gemv!: expected vectors of at least {} and {} elements, found {} and {}",
+                n, m, $x.len(), $y.len());
+            let $x = &$x[..n];
+            for ($car, $row) in $y[..m].iter_mut().zip($a.chunks(ld.max(1))) {
+                let $row = &$row[..n];
+                $body
+            }
+        }
+    };
+}
+
+/// Fused product of a sparse matrix in CSR format with a vector.
+///
+/// `spmv![y = alpha * csr(indptr, indices, data) * x + beta * y]` takes the matrix as its three
+/// compressed-sparse-row arrays, the nonzeros of row `r` being `data[k]` in the columns
+/// `indices[k]` for `k` in `indptr[r]..indptr[r + 1]`, and becomes
+///     for (y, r) in y.iter_mut().zip(indptr.windows(2)) {
+///         let (s, e) = (r[0], r[1]);
+///         *y = alpha * sum(indices[s..e].iter().zip(data[s..e].iter()).map(|(j, a)| *a * x[*j]))
+///             + beta * *y;
+///     }
+///
+/// so each row is a single gathering reduction. The optional parts and the trailing term are as
+/// for `gemv!`, and the indices may be of any integer type.
+#[macro_export]
+macro_rules! spmv {
+    [$y:ident $assign:tt $alpha:tt * csr($p:ident, $j:ident, $d:ident) * $x:ident + $beta:tt * $y2:ident] =>
+        { spmv![@ $y $assign ($alpha *) ($p $j $d) $x (($beta *) $y2)] };
+    [$y:ident $assign:tt $alpha:tt * csr($p:ident, $j:ident, $d:ident) * $x:ident + $y2:ident] =>
+        { spmv![@ $y $assign ($alpha *) ($p $j $d) $x (() $y2)] };
+    [$y:ident $assign:tt $alpha:tt * csr($p:ident, $j:ident, $d:ident) * $x:ident] =>
+        { spmv![@ $y $assign ($alpha *) ($p $j $d) $x ()] };
+    [$y:ident $assign:tt csr($p:ident, $j:ident, $d:ident) * $x:ident + $beta:tt * $y2:ident] =>
+        { spmv![@ $y $assign () ($p $j $d) $x (($beta *) $y2)] };
+    [$y:ident $assign:tt csr($p:ident, $j:ident, $d:ident) * $x:ident + $y2:ident] =>
+        { spmv![@ $y $assign () ($p $j $d) $x (() $y2)] };
+    [$y:ident $assign:tt csr($p:ident, $j:ident, $d:ident) * $x:ident] =>
+        { spmv![@ $y $assign () ($p $j $d) $x ()] };
+
+    // the row's reduction (prefix=@row), then the statement as in gemv!
+    [@row $r:ident ($p:ident $j:ident $d:ident) $x:ident] => {
+        {
+            let (s, e) = ($crate::__rt::index($r[0]), $crate::__rt::index($r[1]));
+            $crate::__rt::sum($j[s..e].iter().zip($d[s..e].iter()).map(|(j, a)| *a * $x[$crate::__rt::index(*j)]))
+        }
+    };
+    [@ $y:ident $assign:tt ($($alpha:tt)*) ($p:ident $j:ident $d:ident) $x:ident (($($beta:tt)*) $y2:ident)] => {
+        {
+            macro_rules! eval {
+                ($y $y) => {};
+                ($y2 $y) => { compile_error!("spmv!: the trailing term must scale the assigned vector") };
+            }
+            eval!($y2 $y);
+            #[allow(clippy::assign_op_pattern)]
+            for (car, r) in $y.iter_mut().zip($p.windows(2)) {
+                *car $assign $($alpha)* spmv![@row r ($p $j $d) $x] + $($beta)* *car;
+            }
+        }
+    };
+    [@ $y:ident $assign:tt ($($alpha:tt)*) ($p:ident $j:ident $d:ident) $x:ident ()] => {
+        for (car, r) in $y.iter_mut().zip($p.windows(2)) {
+            *car $assign $($alpha)* spmv![@row r ($p $j $d) $x];
+        }
+    };
+}
+
+/// Fused product of a banded matrix, stored by diagonals, with a vector.
+///
+/// `tridiagmv![y = dl*shift(x, -1) + d*x + du*shift(x, +1)]` computes
+///     y[i] = dl[i]*x[i - 1] + d[i]*x[i] + du[i]*x[i + 1]
+///
+/// where a term whose element of `x` falls outside `x` counts as zero, so that `dl[0]` and the
+/// last element of `du` are never read. Any number of diagonals may be given, with any constant
+/// shifts, e.g. a pentadiagonal `e*shift(x, -2) + ... + f*shift(x, 2)`, and terms may be
+/// subtracted instead. The rows are those of the shortest of `y` and the diagonals; the interior
+/// rows, all of whose terms are in range, are a single loop over slices free of bounds checks,
+/// and only the few rows at either end test their terms. Elements must be `Copy + Default`, the
+/// default being zero.
+#[macro_export]
+macro_rules! tridiagmv {
+    // normalize every term to [op diagonal x (shift)]
+    [$y:ident $assign:tt - $($rest:tt)+] => { tridiagmv![@ $y $assign () - $($rest)*] };
+    [$y:ident $assign:tt $($rest:tt)+] => { tridiagmv![@ $y $assign () + $($rest)*] };
+    [@ $y:ident $assign:tt ($($t:tt)*) $op:tt $a:ident * shift($x:ident, + $k:literal) $($rest:tt)*] =>
+        { tridiagmv![@ $y $assign ($($t)* [$op $a $x ($k)]) $($rest)*] };
+    [@ $y:ident $assign:tt ($($t:tt)*) $op:tt $a:ident * shift($x:ident, $k:expr) $($rest:tt)*] =>
+        { tridiagmv![@ $y $assign ($($t)* [$op $a $x ($k)]) $($rest)*] };
+    [@ $y:ident $assign:tt ($($t:tt)*) $op:tt $a:ident * $x:ident $($rest:tt)*] =>
+        { tridiagmv![@ $y $assign ($($t)* [$op $a $x (0)]) $($rest)*] };
+    [@ $y:ident $assign:tt ([$op0:tt $a0:ident $x0:ident $k0:tt] $([$op:tt $a:ident $x:ident $k:tt])*)] => {
+        {
+            let mut n = $y.len();
+            n = $crate::__rt::min(n, $a0.len());
+            $( n = $crate::__rt::min(n, $a.len()); )*
+            // the interior rows lo..hi
+            let (mut lo, mut hi) = (0, n);
+            for (k, len) in [($k0 as isize, $x0.len()) $(, ($k as isize, $x.len()))*] {
+                lo = $crate::__rt::max(lo, k.min(0).unsigned_abs());
+                hi = $crate::__rt::min(hi, (len as isize - k).max(0) as usize);
+            }
+            let lo = $crate::__rt::min(lo, n);
+            let hi = $crate::__rt::max(lo, hi);
+            if lo < hi {
+                tridiagmv![@cut (lo hi) $y $assign () [$op0 $a0 $x0 $k0] $([$op $a $x $k])*]
+            }
+            for i in (0..lo).chain(hi..n) {
+                $y[i] $assign tridiagmv![@neg $op0 $crate::__rt::band(&$a0[..], &$x0[..], i, $k0 as isize)]
+                    $($op $crate::__rt::band(&$a[..], &$x[..], i, $k as isize))*;
+            }
+        }
+    };
+
+    // cut every term's diagonal and x to the interior rows, then the loop over the cut slices
+    [@cut ($lo:ident $hi:ident) $y:ident $assign:tt ($($done:tt)*) [$op:tt $a:ident $x:ident $k:tt] $($more:tt)*] => {
+        {
+            let k = $k as isize;
+            let diagonal = &$a[$lo..$hi];
+            let shifted = &$x[($lo as isize + k) as usize..($hi as isize + k) as usize];
+            tridiagmv![@cut ($lo $hi) $y $assign ($($done)* [$op diagonal shifted]) $($more)*]
+        }
+    };
+    [@cut ($lo:ident $hi:ident) $y:ident $assign:tt ([$op0:tt $a0:ident $x0:ident] $([$op:tt $a:ident $x:ident])*)] => {
+        for (j, car) in $y[$lo..$hi].iter_mut().enumerate() {
+            *car $assign tridiagmv![@neg $op0 $a0[j] * $x0[j]] $($op $a[j] * $x[j])*;
+        }
+    };
+    [@neg + $($t:tt)*] => { $($t)* };
+    [@neg - $($t:tt)*] => { -($($t)*) };
+    [@ $y:ident $assign:tt $t:tt $($rest:tt)*] =>
+        { compile_error!(concat!("tridiagmv!: expected `a*shift(x, k)` or `a*x`, found `", stringify!($($rest)*), "`")) };
+}
+
+/// Fused rank-1 update of a row-major matrix buffer.
+///
+/// `ger![A += alpha * x * y^T]` treats `A` as a flat, row-major `&mut [T]` whose row length is
+/// `y.len()`, and becomes
+///     for (row, x) in A.chunks_mut(y.len()).zip(x.iter()) {
+///         let s = alpha * *x;
+///         axpy![row += s * y];
+///     }
+///
+/// so the inner loop is exactly the canonical `axpy!` loop. `alpha` is optional, and `-=` (or `=`)
+/// may be used in place of `+=`. `y` must not be empty.
+#[macro_export]
+macro_rules! ger {
+    [$a:ident $assign:tt $alpha:tt * $x:ident * $y:ident ^ T] => {
+        for (row, car) in $a.chunks_mut($y.len()).zip($x.iter()) {
+            let s = $alpha * *car;
+            axpy![row $assign s * $y];
+        }
+    };
+    [$a:ident $assign:tt $x:ident * $y:ident ^ T] => {
+        for (row, car) in $a.chunks_mut($y.len()).zip($x.iter()) {
+            let s = *car;
+            axpy![row $assign s * $y];
+        }
+    };
+}
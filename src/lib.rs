@@ -1,5 +1,10 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 /// Exposes linear combinations of slice-like objects of Copy values to LLVM's auto-vectorizer,
 /// a.k.a. write vector expressions as you would in Matlab or Fortran.
 ///
@@ -20,112 +25,2344 @@
 ///     }
 ///
 /// If `x`, `y`, and `z` are slices, bounds-checks are known to be elided, resulting in fairly
-/// optimal code. The value of the macro is that any combination-like expression is generated, e.g.
+/// optimal code. In general an operand may be anything with `iter()` (and the target anything with
+/// `iter_mut()`), either as inherent methods or through the `AxpySource` and `AxpyTarget` traits.
+/// The value of the macro is that any combination-like expression is generated, e.g.
 /// `w = 2.0 * x - z` becomes
 ///     for (x, (z, w)) in x.iter().zip(z.iter().zip(w.iter_mut())) {
 ///         *w = 2.0 * *x - *z;
 ///     }
 ///
-/// In addition to `=`, both `+=` and `-=` are supported. (Technically *any* assignment operator
-/// works, e.g. `/=`, but that is an accident of implementation rather than an intended feature.)
-/// The assigned variable may appear anywhere in the constructed expression, as the macro is
-/// designed to take appropriate care of the mutable borrow. Coefficients may be compatible scalar
-/// literals or variables.
-
-
+/// The grammar, with `x` a vector (an identifier), `a` and `d` scalars (a literal, an identifier or
+/// a parenthesized expression, optionally negated) and `[...]` optional:
+///     invocation := axpy![[prefix: ...] statement] | axpy![n; statement]
+///                 | axpy!{[block(w):] let t = rhs; ... statement; ...}
+///     statement  := [&mut] target op rhs       (several, separated by `;`, after `tile:`)
+///     target     := x | x[i] | self.x | (expr) | &mut expr | col(..) | channel(..) | panel(..)
+///     op         := = | += | -= | .*= | ./=
+///     rhs        := [±] term ± term ... | (rhs) / d | clamp(rhs, lo, hi) | max(rhs, ...)
+///                 | min(rhs, ...) | abs(rhs) | operand cmp rhs | (rhs) cmp rhs
+///     term       := operand | a*operand | operand*a | term / d
+///     operand    := x | f(x) | x as T | iter(it) | rev(x) | rot(x, k) | x(k) | sparse(idx, vals)
+///                 | col(x, j, ld) | channel(x, c, k) | panel(x, m, n, ld) | m!(args)
+///     cmp        := < | <= | > | >= | == | !=
+///
+/// The prefixes, of which several may be combined, e.g. `hook(h): tile(4096):`, and the other
+/// forms are described in the `guide` module, or in a module of their own:
+///
+/// - lengths: `strict:`, `checked:`, `truncate:`, `exact:` and `n;` (guide, Lengths)
+/// - element types: `complex:` (`complex`), `checked_ops:`, `rug:` (guide, Coefficients and
+///   element types), `stochastic(rng):` (`stochastic`), `quant:` (`quant`)
+/// - containers: `nd:`, `faer:`, `components(T):`, `for rows:`, `for rows(n):`, `ptr(n):` (guide,
+///   Other containers), `ring:` (`ring`), `atomic:` (`atomic`), `mmap:` (`mmap`)
+/// - operands: `f(x)`, `iter(it)`, `rev(x)`, `rot(x, k)`, `x(k)` with `stencil(policy):`,
+///   `sparse(idx, vals)`, `col`, `channel`, `panel` (guide, Operands)
+/// - right-hand sides: `clamp`, `max`, `min`, `abs` and comparisons (guide, Bounds, extrema and
+///   masks), coefficients read with the loop index (`smooth`), `extend(s):` and `each(f):` sinks
+///   (guide, Targets and assignments)
+/// - evaluation: `tail(strategy, w):`, `tile(b):`, `let` blocks with `block(w):`, `hook(h):`
+///   (guide, Chunks, tiles and temporaries), `par(e):` (`exec`)
+/// - kernels: `x86:`, `aarch64:`, `riscv64:`, `wasm32:`, `cuda(h):` and `reproducible:` (guide,
+///   Architecture kernels)
+/// - debugging: `checked_fp:`, `volatile:`, `axpy_expand!`, `axpy_at!` (guide, Debugging and
+///   hardware buffers)
 #[macro_export]
 macro_rules! axpy {
     // point of entry to the macro: we immediately hand the input off to the parser (prefix=!)
     // `+ .` is used as terminal indicator
-    [$y:ident $assign:tt $($rest:tt)+] => { axpy![! $y $assign () $($rest)* + .] };
+    // everything the parser does not need to look at rides along in a header group:
+    //     (kind mode target assign outer)
+    // kind:  `emit` to emit the generated code, `expand` to also return it as a string, or either
+    //        paired with the name bound to the loop index, e.g. `(emit i)` for `z[i] = ...`
+    // mode:  `()` for slices, `(ptr n)` for raw pointers, `(rows)` for slice-of-rows matrices,
+    //        `(new)` for a freshly allocated result, `(fold f)` for the result of
+    //        `__rt::f` on the iterator of right-hand side values, `(sink f s)` for the same with
+    //        the sink `s` passed first
+    // outer: `()`, or the function applied to the whole right-hand side (see below)
+    [extend($s:expr): $($rest:tt)+] =>
+        { { let sink = &mut $s; axpy![! (emit (sink extend sink) __axpy_sink = ()) () $($rest)* + .] } };
+    [each($f:expr): $($rest:tt)+] =>
+        { { let sink = &mut $f; axpy![! (emit (sink each sink) __axpy_sink = ()) () $($rest)* + .] } };
+    [ptr($n:expr): $y:ident [$i:ident] $assign:tt $($rest:tt)+] => {
+        { let n: usize = $n; axpy![! ((emit $i) (ptr n) $y $assign ()) () $($rest)* + .] }
+    };
+    [ptr($n:expr): $y:ident $assign:tt $($rest:tt)+] => {
+        { let n: usize = $n; axpy![! (emit (ptr n) $y $assign ()) () $($rest)* + .] }
+    };
+    [hook($f:expr): $($rest:tt)+] => { { let hook = &$f; axpy![;hooked (hook) $($rest)*] } };
+    [let $($rest:tt)+] => { axpy![;hooked () let $($rest)*] };
+    [block($w:expr): let $($rest:tt)+] => { axpy![;hooked () block($w): let $($rest)*] };
+    [tile: $($rest:tt)+] => { axpy![;hooked () tile: $($rest)*] };
+    [tile(auto): $($rest:tt)+] => { axpy![;hooked () tile(auto): $($rest)*] };
+    [tile($b:expr): $($rest:tt)+] => { axpy![;hooked () tile($b): $($rest)*] };
+    [par for rows($n:expr): $($rest:tt)+] => { axpy![;hooked () par for rows($n): $($rest)*] };
+    [par($e:expr) for rows($n:expr): $($rest:tt)+] => { axpy![;hooked () par($e) for rows($n): $($rest)*] };
+    [par($e:expr): $($rest:tt)+] => { axpy![;hooked () par($e): $($rest)*] };
+    // the chunked evaluators, with the hook `(f)` called after every chunk, or `()`
+    [;hooked $h:tt let $($rest:tt)+] => { axpy![;hooked $h block(64): let $($rest)*] };
+    [;hooked $h:tt block($w:expr): let $($rest:tt)+] => { axpy![;block $w; $h; () () let $($rest)* ;] };
+    [;hooked $h:tt tile: $($rest:tt)+] => { axpy![;hooked $h tile(1024): $($rest)*] };
+    [;hooked $h:tt tile(auto): $($rest:tt)+] => {
+        {
+            static TUNER: $crate::__rt::Tuner = $crate::__rt::Tuner::new();
+            let probe = TUNER.start();
+            axpy![;tile probe.block(); (TUNER probe); $h; () () $($rest)* ;]
+        }
+    };
+    [;hooked $h:tt tile($b:expr): $($rest:tt)+] => { axpy![;tile $b; (); $h; () () $($rest)* ;] };
+    [;hooked $h:tt par for rows($n:expr): $($rest:tt)+] =>
+        { axpy![;hooked $h par($crate::exec::Threads) for rows($n): $($rest)*] };
+    [;hooked $h:tt par($e:expr) for rows($n:expr): $y:ident $assign:tt $($rest:tt)+] =>
+        { { let exec = &$e; let n: usize = $n; axpy![! (emit (par exec $h n) $y $assign ()) () $($rest)* + .] } };
+    [;hooked $h:tt par($e:expr): $y:ident $assign:tt $($rest:tt)+] =>
+        { { let exec = &$e; axpy![! (emit (par exec $h) $y $assign ()) () $($rest)* + .] } };
+    [;hooked $h:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy!: hook: expected `tile`, `par` or a block with temporaries, found `", stringify!($($rest)*), "`")) };
+    [;hook () $s:ident $e:ident $body:block] => { $body };
+    [;hook ($h:ident) $s:ident $e:ident $body:block] => {
+        {
+            let t = $crate::__rt::Instant::now();
+            $body;
+            $h($s, $e - $s, t.elapsed());
+        }
+    };
+    [panel($y:ident, $r:expr, $c:expr, $ld:expr) $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (panel row ($r) ($c) ($ld)) $y $assign ()) () $($rest)* + .] };
+    [channel($y:ident, $c:expr, $n:expr) $assign:tt $($rest:tt)+] => { axpy![col($y, $c, $n) $assign $($rest)*] };
+    [col($y:ident, $j:expr, $lda:expr) $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (col ($j) ($lda)) $y $assign ()) () $($rest)* + .] };
+    [tail($t:ident, $w:expr): $y:ident $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (tail $t ($w)) $y $assign ()) () $($rest)* + .] };
+    [x86: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (arch x86) $y $assign ()) () $($rest)* + .] };
+    [aarch64: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (arch aarch64) $y $assign ()) () $($rest)* + .] };
+    [riscv64: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (arch riscv64) $y $assign ()) () $($rest)* + .] };
+    [wasm32: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (arch wasm32) $y $assign ()) () $($rest)* + .] };
+    [cuda($h:ident): $y:ident $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (arch (cuda $h)) $y $assign ()) () $($rest)* + .] };
+    [reproducible: x86: $($rest:tt)+] => { axpy![reproducible: $($rest)*] };
+    [reproducible: aarch64: $($rest:tt)+] => { axpy![reproducible: $($rest)*] };
+    [reproducible: riscv64: $($rest:tt)+] => { axpy![reproducible: $($rest)*] };
+    [reproducible: wasm32: $($rest:tt)+] => { axpy![reproducible: $($rest)*] };
+    [reproducible: cuda($h:ident): $($rest:tt)+] =>
+        { compile_error!("axpy!: reproducible: cuBLAS does not guarantee an evaluation order, drop `cuda(h):`") };
+    [reproducible: $($rest:tt)+] => { axpy![$($rest)*] };
+    [ring: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (ring) $y $assign ()) () $($rest)* + .] };
+    [checked_ops: $y:ident $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (checked_ops) $y $assign ()) () $($rest)* + .] };
+    [volatile: $y:ident $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (volatile) $y $assign ()) () $($rest)* + .] };
+    [checked_fp: $y:ident $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (checked_fp) $y $assign ()) () $($rest)* + .] };
+    [stochastic($r:ident): $y:ident $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (stochastic $r) $y $assign ()) () $($rest)* + .] };
+    [components($s:ident): $y:ident $assign:tt $($rest:tt)+] =>
+        { axpy![! (emit (components $s) $y $assign ()) () $($rest)* + .] };
+    [stencil($p:ident): $y:ident $assign:tt $($rest:tt)+] => { axpy![;shift shift () ($p $y $assign) () $($rest)*] };
+    [atomic: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (atomic) $y $assign ()) () $($rest)* + .] };
+    [mmap: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (mmap) $y $assign ()) () $($rest)* + .] };
+    [rug: $y:ident $assign:tt $($rest:tt)+] => { $crate::__axpy_rug![$y $assign $($rest)*] };
+    [faer: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (faer) $y $assign ()) () $($rest)* + .] };
+    [nd: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (nd) $y $assign ()) () $($rest)* + .] };
+    [quant: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (quant) $y $assign ()) () $($rest)* + .] };
+    [complex: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (complex) $y $assign ()) () $($rest)* + .] };
+    [for rows: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (rows) $y $assign ()) () $($rest)* + .] };
+    [for rows($n:expr): $y:ident $assign:tt $($rest:tt)+] =>
+        { { let n: usize = $n; axpy![! (emit (rows n) $y $assign ()) () $($rest)* + .] } };
+    [strict: $($rest:tt)+] => { axpy![@len strict; $($rest)*] };
+    [checked: $($rest:tt)+] => { axpy![@len checked; $($rest)*] };
+    [exact: $($rest:tt)+] => { axpy![@len exact; $($rest)*] };
+    [truncate: $y:ident [$i:ident] $assign:tt $($rest:tt)+] => { axpy![! ((emit $i) () $y $assign ()) () $($rest)* + .] };
+    [truncate: $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit () $y $assign ()) () $($rest)* + .] };
+    [$n:ident; $($rest:tt)+] => { axpy![@upto ($n) $($rest)*] };
+    [$n:literal; $($rest:tt)+] => { axpy![@upto ($n) $($rest)*] };
+    [($($n:tt)+); $($rest:tt)+] => { axpy![@upto ($($n)*) $($rest)*] };
+    [$y:ident += sparse($i:ident, $v:ident)] => { axpy![@sparse $y += $i $v ()] };
+    [$y:ident -= sparse($i:ident, $v:ident)] => { axpy![@sparse $y -= $i $v ()] };
+    [$y:ident += $a:tt * sparse($i:ident, $v:ident)] => { axpy![@sparse $y += $i $v ($a *)] };
+    [$y:ident -= $a:tt * sparse($i:ident, $v:ident)] => { axpy![@sparse $y -= $i $v ($a *)] };
+    [$y:ident $assign:tt sparse($i:ident, $v:ident)] => { axpy![@sparse $assign] };
+    [$y:ident $assign:tt $a:tt * sparse($i:ident, $v:ident)] => { axpy![@sparse $assign] };
+    [@sparse $assign:tt] =>
+        { compile_error!(concat!("axpy!: a sparse operand is added with `+=` or `-=`, not `", stringify!($assign), "`")) };
+    [@sparse $y:ident $assign:tt $i:ident $v:ident ($($a:tt)*)] => {
+        for (i, v) in $i.iter().zip($v.iter()) {
+            $y[$crate::__rt::index(*i)] $assign $($a)* *v;
+        }
+    };
+    [&mut $y:ident [$i:ident] $($rest:tt)+] => { { axpy![$y [$i] $($rest)*]; &mut $y[..] } };
+    [&mut $y:ident [$($r:tt)*] $($rest:tt)+] => { axpy![@lhs ($y [$($r)*]) $($rest)*] };
+    [&mut $y:ident $assign:tt $($rest:tt)+] => { { axpy![$y $assign $($rest)*]; &mut $y[..] } };
+    [$y:ident . $f:ident $($rest:tt)+] => { axpy![@lhs ($y . $f) $($rest)*] };
+    [($($l:tt)+) $($rest:tt)+] => { axpy![@lhs ($($l)*) $($rest)*] };
+    [$y:ident [$i:ident] $assign:tt $($rest:tt)+] => { $crate::__axpy_len![$y [$i] $assign $($rest)*] };
+    [$y:ident [$($r:tt)*] $($rest:tt)+] => { axpy![@lhs ($y [$($r)*]) $($rest)*] };
+    [$y:ident $assign:tt $($rest:tt)+] => { $crate::__axpy_len![$y $assign $($rest)*] };
+    [@upto $n:tt $y:ident [$i:ident] $assign:tt $($rest:tt)+] =>
+        { { let n: usize = $n; axpy![! ((emit $i) (upto n) $y $assign ()) () $($rest)* + .] } };
+    [@upto $n:tt $y:ident $assign:tt $($rest:tt)+] =>
+        { { let n: usize = $n; axpy![! (emit (upto n) $y $assign ()) () $($rest)* + .] } };
+    [@len $p:ident; $y:ident [$i:ident] $assign:tt $($rest:tt)+] =>
+        { axpy![! ((emit $i) (len $p) $y $assign ()) () $($rest)* + .] };
+    [@len $p:ident; $y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (len $p) $y $assign ()) () $($rest)* + .] };
+
+    // an expression target (prefix=@lhs) is collected up to the assignment operator and borrowed
+    // once as `target`; the right-hand side is then split into terms (prefix=@rhs) and those that
+    // are the target expression itself, possibly scaled, are rewritten to use `target` instead
+    [@lhs $l:tt = $($rest:tt)+] => { axpy![@lhs $l (=) $($rest)*] };
+    [@lhs $l:tt += $($rest:tt)+] => { axpy![@lhs $l (+=) $($rest)*] };
+    [@lhs $l:tt -= $($rest:tt)+] => { axpy![@lhs $l (-=) $($rest)*] };
+    [@lhs $l:tt . *= $($rest:tt)+] => { axpy![@lhs $l (. *=) $($rest)*] };
+    [@lhs $l:tt . /= $($rest:tt)+] => { axpy![@lhs $l (. /=) $($rest)*] };
+    [@lhs ($($l:tt)+) ($($op:tt)+) $($rest:tt)+] => {
+        {
+            let target = &mut $($l)*;
+            macro_rules! is {
+                ($k:tt ($($l)*)) => { axpy![@is $k lhs] };
+                ($k:tt ($a:tt * $($l)*)) => { axpy![@is $k ($a)] };
+                ($k:tt $t:tt) => { axpy![@is $k ()] };
+            }
+            axpy![@rhs (target ($($op)*)) () () $($rest)*]
+        }
+    };
+    [@lhs ($($l:tt)*) $t:tt $($rest:tt)*] => { axpy![@lhs ($($l)* $t) $($rest)*] };
+    [@lhs ($($l:tt)*)] =>
+        { compile_error!(concat!("axpy!: expected an assignment to `", stringify!($($l)*), "`")) };
+    [@rhs $st:tt ($($out:tt)*) () + $($rest:tt)*] => { axpy![@rhs $st ($($out)* +) () $($rest)*] };
+    [@rhs $st:tt ($($out:tt)*) () - $($rest:tt)*] => { axpy![@rhs $st ($($out)* -) () $($rest)*] };
+    [@rhs $st:tt $out:tt ($($t:tt)+) + $($rest:tt)*] => { is![($st $out ($($t)*) (+) ($($rest)*)) ($($t)*)] };
+    [@rhs $st:tt $out:tt ($($t:tt)+) - $($rest:tt)*] => { is![($st $out ($($t)*) (-) ($($rest)*)) ($($t)*)] };
+    [@rhs $st:tt $out:tt ($($t:tt)+)] => { is![($st $out ($($t)*) () ()) ($($t)*)] };
+    [@rhs ($y:ident ($($op:tt)*)) ($($out:tt)*) ()] => { axpy![$y $($op)* $($out)*] };
+    [@rhs $st:tt $out:tt ($($t:tt)*) $u:tt $($rest:tt)*] => { axpy![@rhs $st $out ($($t)* $u) $($rest)*] };
+    // (the verdict of `is!` on a term)
+    [@is (($y:ident $op:tt) ($($out:tt)*) $t:tt ($($sep:tt)*) ($($rest:tt)*)) lhs] =>
+        { axpy![@rhs ($y $op) ($($out)* $y $($sep)*) () $($rest)*] };
+    [@is (($y:ident $op:tt) ($($out:tt)*) $t:tt ($($sep:tt)*) ($($rest:tt)*)) ($a:tt)] =>
+        { axpy![@rhs ($y $op) ($($out)* $a * $y $($sep)*) () $($rest)*] };
+    [@is ($st:tt ($($out:tt)*) ($($t:tt)*) ($($sep:tt)*) ($($rest:tt)*)) ()] =>
+        { axpy![@rhs $st ($($out)* $($t)* $($sep)*) () $($rest)*] };
 
 
-    // parser rules: recursively perform the following transformations to the tokens
-    // +? x     =>   1 * x
-    // -  x     =>  -1 * x
+    // the elementwise assignments `.*=` and `./=` arrive as `.` followed by the operator, and are
+    // plain `*=` and `/=` on elements
+    [! ($k:tt $m:tt $y:ident . ()) () *= $($rest:tt)+] => { axpy![! ($k $m $y *= ()) () $($rest)*] };
+    [! ($k:tt $m:tt $y:ident . ()) () /= $($rest:tt)+] => { axpy![! ($k $m $y /= ()) () $($rest)*] };
+
+    // distribution of a divisor over a group: every term gets `/ d`, and if the group is
+    // subtracted (s is `-`) every sign is flipped, an unsigned leading term counting as `+`
+    [/ $d:tt + () (+ $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d + (+) ($($g)*) $k $r] };
+    [/ $d:tt + () (- $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d + (-) ($($g)*) $k $r] };
+    [/ $d:tt - () (+ $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d - (-) ($($g)*) $k $r] };
+    [/ $d:tt - () (- $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d - (+) ($($g)*) $k $r] };
+    [/ $d:tt $s:tt () ($($g:tt)+) $k:tt $r:tt] => { axpy![/ $d $s () (+ $($g)*) $k $r] };
+    [/ $d:tt + ($($out:tt)+) (+ $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d + ($($out)* / $d +) ($($g)*) $k $r] };
+    [/ $d:tt + ($($out:tt)+) (- $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d + ($($out)* / $d -) ($($g)*) $k $r] };
+    [/ $d:tt - ($($out:tt)+) (+ $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d - ($($out)* / $d -) ($($g)*) $k $r] };
+    [/ $d:tt - ($($out:tt)+) (- $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d - ($($out)* / $d +) ($($g)*) $k $r] };
+    [/ $d:tt $s:tt ($($out:tt)+) ($t:tt $($g:tt)*) $k:tt $r:tt] => { axpy![/ $d $s ($($out)* $t) ($($g)*) $k $r] };
+    [/ $d:tt $s:tt ($($out:tt)+) () ($($k:tt)*) ($($r:tt)*)] => { axpy![$($k)* $($out)* / $d $($r)*] };
+
+    // outer functions of the whole right-hand side, i.e. clamp(e, lo, hi), min(e, f, ...),
+    // max(e, f, ...) and abs(e) (unless e is a single operand x, read as x.abs()), are recorded in
+    // the header and their arguments parsed as `,`-separated combinations; clamp's scalar bounds
+    // (single tts like coefficients, optionally negated) are split off first
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () clamp($($args:tt)+) + .] =>
+        { axpy![=clamp ($k $m $y $assign) () $($args)*] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () min($($args:tt)+) + .] =>
+        { axpy![! ($k $m $y $assign (min)) () $($args)* + .] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () max($($args:tt)+) + .] =>
+        { axpy![! ($k $m $y $assign (max)) () $($args)* + .] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () abs($a:tt $($args:tt)+) + .] =>
+        { axpy![! ($k $m $y $assign (abs)) () $a $($args)* + .] };
+    // comparisons of a single operand or a parenthesized combination, e.g. (x - y) > tol, with a
+    // scalar (a single tt, optionally negated) or another combination, which are also recorded in
+    // the header, as (cmp op t) or (cmp op)
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () $l:tt > $($r:tt)+] => { axpy![=cmp ($k $m $y $assign) (>) $l () $($r)*] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () $l:tt < $($r:tt)+] => { axpy![=cmp ($k $m $y $assign) (<) $l () $($r)*] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () $l:tt >= $($r:tt)+] => { axpy![=cmp ($k $m $y $assign) (>=) $l () $($r)*] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () $l:tt <= $($r:tt)+] => { axpy![=cmp ($k $m $y $assign) (<=) $l () $($r)*] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () $l:tt == $($r:tt)+] => { axpy![=cmp ($k $m $y $assign) (==) $l () $($r)*] };
+    [! ($k:tt $m:tt $y:ident $assign:tt ()) () $l:tt != $($r:tt)+] => { axpy![=cmp ($k $m $y $assign) (!=) $l () $($r)*] };
+    // (the right side is taken up to the terminal `+ .`)
+    [=cmp $h:tt $op:tt $l:tt ($($r:tt)*) + .] => { axpy![=cmp $h $op $l ($($r)*)] };
+    [=cmp $h:tt $op:tt $l:tt ($($r:tt)*) $t:tt $($rest:tt)+] => { axpy![=cmp $h $op $l ($($r)* $t) $($rest)*] };
+    [=cmp $h:tt $op:tt $x:ident $r:tt] => { axpy![=cmp $h $op ($x) $r] };
+    [=cmp ($($h:tt)*) ($op:tt) ($($l:tt)+) ($t:tt)] => { axpy![! ($($h)* (cmp $op $t)) () $($l)* + .] };
+    [=cmp ($($h:tt)*) ($op:tt) ($($l:tt)+) (- $t:tt)] => { axpy![! ($($h)* (cmp $op (-$t))) () $($l)* + .] };
+    [=cmp ($($h:tt)*) ($op:tt) ($($l:tt)+) ($($r:tt)+)] => { axpy![! ($($h)* (cmp $op)) () $($l)* , $($r)* + .] };
+    [=clamp ($($h:tt)*) ($($e:tt)*) , $lo:tt , $hi:tt] =>
+        { axpy![! ($($h)* (clamp $lo $hi)) () $($e)* + .] };
+    [=clamp ($($h:tt)*) ($($e:tt)*) , - $lo:tt , $hi:tt] =>
+        { axpy![! ($($h)* (clamp (-$lo) $hi)) () $($e)* + .] };
+    [=clamp ($($h:tt)*) ($($e:tt)*) , $lo:tt , - $hi:tt] =>
+        { axpy![! ($($h)* (clamp $lo (-$hi))) () $($e)* + .] };
+    [=clamp ($($h:tt)*) ($($e:tt)*) , - $lo:tt , - $hi:tt] =>
+        { axpy![! ($($h)* (clamp (-$lo) (-$hi))) () $($e)* + .] };
+    [=clamp ($($h:tt)*) ($($e:tt)*) $t:tt $($rest:tt)*] =>
+        { axpy![=clamp ($($h)*) ($($e)* $t) $($rest)*] };
+
+    // parser rules: recursively peel off one term at a time, performing the transformations
+    // +? x     =>   0 + x
+    // -  x     =>   0 - x
     // +? a * x =>   a * x
-    // -  a * x =>  -a * x
-    // implementation note: 3 tokens are required to fully disambiguate the patterns,
-    //                      that's why we seemingly peel back unnecessary tokens.
-    [! $y:ident $assign:tt ($($parsed:tt)*)   $x:ident + $($rest:tt)+]       => // "x + ..."
-        { axpy![! $y $assign ($($parsed)*     0 + $x) + $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*)   $x:ident - $($rest:tt)+]       => // "x - ..."
-        { axpy![! $y $assign ($($parsed)*     0 + $x) - $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*) + $x:ident + $($rest:tt)+]       => // "+ x + ..."
-        { axpy![! $y $assign ($($parsed)*     0 + $x) + $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*) + $x:ident - $($rest:tt)+]       => // "+ x - ..."
-        { axpy![! $y $assign ($($parsed)*     0 + $x) - $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*) - $x:ident + $($rest:tt)+]       => // "- x + ..."
-        { axpy![! $y $assign ($($parsed)*     0 - $x) + $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*) - $x:ident - $($rest:tt)+]       => // "- x - ..."
-        { axpy![! $y $assign ($($parsed)*     0 - $x) - $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*)   $a:tt * $x:ident $($rest:tt)+] => // "a * x ..."
-        { axpy![! $y $assign ($($parsed)*    $a * $x) $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*) + $a:tt * $x:ident $($rest:tt)+] => // "+ a * x ..."
-        { axpy![! $y $assign ($($parsed)*    $a * $x) $($rest)*] };
-    [! $y:ident $assign:tt ($($parsed:tt)*) - $a:tt * $x:ident $($rest:tt)+] => // "- a * x ..."
-        { axpy![! $y $assign ($($parsed)* (-$a) * $x) $($rest)*] };
-
-    // upon conclusion of parsing, we hand off to iterator construction
-    // (prefix=@) and expression constructor (prefix=#)
-    [! $y:ident $assign:tt ($($parsed:tt)+) + .] => {
-        for (car,cdr) in axpy![@ $y; $y.iter_mut(); $($parsed)*] {
-            *car $assign axpy![# $y; car; cdr; () $($parsed)*];
-        }
-    };
-
-
-    // iterator construction: we need to emit a zipped
-    // iterator for x != y, and do nothing when x = y
-    // (since y has already been borrowed mutably)
-    [@ $y:ident; $iter:expr; ] => { $iter.map(|x| (x,)) };
-    [@ $y:ident; $iter:expr; $a:tt $op:tt $x:ident $($rest:tt)*] => {
+    // -  a * x =>  (-a) * x
+    // where every canonical term is followed by the (possibly empty) group of tokens to apply to
+    // the element of x, e.g. `()` for x itself, `(.sqrt())` for the operand `sqrt(x)` and
+    // `(as f32)` for the operand `x as f32`.
+    // (with coefficients on both sides, `a*x*b`, the element is scaled by b first)
+    [! $h:tt ($($parsed:tt)*) {$a:tt *} $x:ident * $b:tt $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $a * $x (* $b)) $($rest)*] };
+    // (a parenthesized combination divided by a scalar, `(x - y) / h`, is distributed over its
+    // terms, prefix=/, flipping their signs if it is subtracted)
+    [! $h:tt ($($parsed:tt)*) {0 $s:tt} ($($g:tt)+) / $d:tt $($rest:tt)+] =>
+        { axpy![/ $d $s () ($($g)*) (! $h ($($parsed)*)) ($($rest)*)] };
+    [! $h:tt $p:tt {$a:tt *} ($($g:tt)+) / $d:tt $($rest:tt)+] =>
+        { compile_error!("axpy!: a divided combination cannot have a coefficient, divide by it instead") };
+    // A term's operand ...
+    [! $h:tt $p:tt {$($pre:tt)*} channel($x:ident, $c:expr, $n:expr) $($rest:tt)+] =>
+        { axpy![! $h $p {$($pre)*} col($x, $c, $n) $($rest)*] };
+    // (a rotated operand rot(x, k) is read through the local macro named in the mode, which the
+    // first one switches to, recording every rotation)
+    // (the mode keeps the length policy, if any)
+    [! ($k:tt () $y:ident $assign:tt $o:tt) $p:tt {$($pre:tt)*} rot($x:ident, $($n:tt)+) $($rest:tt)+] =>
+        { axpy![! ($k (rot () rotated) $y $assign $o) $p {$($pre)*} rot($x, $($n)*) $($rest)*] };
+    [! ($k:tt (len $l:ident) $y:ident $assign:tt $o:tt) $p:tt {$($pre:tt)*} rot($x:ident, $($n:tt)+) $($rest:tt)+] =>
+        { axpy![! ($k (rot (len $l) rotated) $y $assign $o) $p {$($pre)*} rot($x, $($n)*) $($rest)*] };
+    [! ($k:tt (rot $l:tt $r:ident $($rs:tt)*) $y:ident $assign:tt $o:tt) ($($parsed:tt)*) {$($pre:tt)*} rot($x:ident, $($n:tt)+) $($rest:tt)+] =>
+        { axpy![! ($k (rot $l $r $($rs)* [$x ($($n)*)]) $y $assign $o) ($($parsed)* $($pre)* $r (mac ($x ($($n)*)))) $($rest)*] };
+    // (likewise, a panel operand panel(x, rows, cols, ld) is read through the local macro named in
+    // the mode of a panel target)
+    [! ($k:tt (panel $m:ident $($ps:tt)*) $y:ident $assign:tt $o:tt) ($($parsed:tt)*) {$($pre:tt)*} panel($x:ident, $($a:tt)+) $($rest:tt)+] =>
+        { axpy![! ($k (panel $m $($ps)* [$x ($($a)*)]) $y $assign $o) ($($parsed)* $($pre)* $m (mac ($x ($($a)*)))) $($rest)*] };
+    [! $h:tt $p:tt {$($pre:tt)*} panel($x:ident, $($a:tt)+) $($rest:tt)+] =>
+        { compile_error!("axpy!: panel operands need a panel target") };
+    [! $h:tt $p:tt {$($pre:tt)*} rot($x:ident, $($n:tt)+) $($rest:tt)+] =>
+        { compile_error!("axpy!: rot operands are supported in plain statements only") };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} col($x:ident, $j:expr, $lda:expr) $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x (col ($j) ($lda))) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} $m:ident ! $a:tt $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $m (mac $a)) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} rev($x:ident) $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x (rev)) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} iter($x:ident) $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x (iter)) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} $f:ident (iter($x:ident)) $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x (iter .$f())) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} $x:ident as $t:ident $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x (as $t)) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} $f:ident ($x:ident) $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x (.$f())) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} $x:ident / $d:tt $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x (div $d)) $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) {$($pre:tt)*} $x:ident $($rest:tt)+] =>
+        { axpy![! $h ($($parsed)* $($pre)* $x ()) $($rest)*] };
+    // A coefficient may also follow its operand if it is a literal or parenthesized, `x*2.` or
+    // `x*(b)`, which at this point looks like a non-operand preceded by x as its coefficient; the
+    // two are swapped (whereas in `x*b` either one could be the vector, and b is taken to be it)
+    [! $h:tt $p:tt {$v:ident *} $b:tt $($rest:tt)+] => { axpy![! $h $p {$b *} $v $($rest)*] };
+    [! $h:tt $p:tt {(-$v:ident) *} $b:tt $($rest:tt)+] => { axpy![! $h $p {(-$b) *} $v $($rest)*] };
+    // (anything else in place of the operand is a mistake)
+    [! $h:tt $p:tt {$($pre:tt)*} + .] =>
+        { compile_error!("axpy!: the expression ends in a dangling operator") };
+    [! $h:tt $p:tt {$($pre:tt)*} $t:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy!: expected a vector operand, found `", stringify!($t), "`")) };
+    // ... is preceded by its coefficient ...
+    [! $h:tt ($($parsed:tt)*) [+] $a:tt * $($rest:tt)+] => { axpy![! $h ($($parsed)*) {$a *} $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) [-] $a:tt * $($rest:tt)+] => { axpy![! $h ($($parsed)*) {(-$a) *} $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) [+] $($rest:tt)+] => { axpy![! $h ($($parsed)*) {0 +} $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) [-] $($rest:tt)+] => { axpy![! $h ($($parsed)*) {0 -} $($rest)*] };
+    // upon conclusion of parsing, we hand off to the lowering (prefix=#),
+    // or to row-wise evaluation (prefix=&)
+    [! ($k:tt (rows $($n:ident)?) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (rows $($n)?) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (par $x:ident $h:tt $($n:ident)?) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (par $x $h $($n)?) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (len exact) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (len exact $k) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (const) $y:ident $assign:tt ()) ($a:tt $op:tt $x:ident $($parsed:tt)*) + .] =>
+        { axpy![&const i $x; () $a $op $x $($parsed)*] };
+    [! ($k:tt (const) $y:ident $assign:tt $o:tt) $parsed:tt + .] =>
+        { compile_error!("axpy_const!: clamp, min and max are not supported") };
+    [! ($k:tt (soa) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (soa) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (checked_ops) $y:ident $assign:tt ()) ($($parsed:tt)+) + .] => {
+        axpy![& (checked_ops) $y () ($($parsed)*); $assign () ($($parsed)*)]
+    };
+    [! ($k:tt (blocks $b:ident $out:ident $f:ident) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (blocks $b $out $f) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (volatile) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (volatile) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (checked_fp) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (checked_fp) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (stochastic $r:ident) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (stochastic $r) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (components $s:ident) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (components $s) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (block let $w:tt ($($t:ident)*) $z:tt ($($done:tt)*) ($($more:tt)*)) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] =>
+        { axpy![;parse $w ($($t)* $y) $z ($($done)* [$y $assign $o ($($parsed)*)]) $($more)*] };
+    [! ($k:tt (block set $w:tt $t:tt ($($z:ident)*) ($($done:tt)*) ($($more:tt)*)) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
         {
             macro_rules! eval {
-                ($y $y) => { axpy![@ $y; $iter; $($rest)*] };
-                ($x $y) => { $iter.zip(axpy![@ $y; $x.iter(); $($rest)*]) };
+                $( ($p:tt $q:tt $z) => { axpy!$p }; )*
+                ($p:tt $q:tt $y) => { axpy!$q };
             }
-            eval!($x $y)
+            eval!([;parse $w $t ($($z)*) ($($done)* [$y $assign $o ($($parsed)*)]) $($more)*]
+                  [;parse $w $t ($($z)* $y) ($($done)* [$y $assign $o ($($parsed)*)]) $($more)*] $y)
         }
     };
+    [! ($k:tt (atomic) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (atomic) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (at $i:ident) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (at $i) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (mmap) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (mmap) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (rug) $y:ident $assign:tt ()) ($($parsed:tt)+) + .] => {
+        axpy![& (rug) $y () ($($parsed)*); $assign () ($($parsed)*)]
+    };
+    [! ($k:tt (rug) $y:ident $assign:tt $o:tt) $parsed:tt + .] =>
+        { compile_error!("axpy!: rug: clamp, min, max and abs are not supported") };
+    [! ($k:tt (faer) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (faer) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (nd) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (nd) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (quant) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (quant) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (complex) $y:ident $assign:tt ()) ($($parsed:tt)+) + .] => {
+        axpy![& (complex) $y () ($($parsed)*); $assign () ($($parsed)*)]
+    };
+    [! ($k:tt (upto $n:ident) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (upto $n $k) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (tail $t:ident $w:tt) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (tail $t $w) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (arch $m:tt) $y:ident $assign:tt ()) ($($parsed:tt)+) + .] =>
+        { axpy![&arch $m $y $assign () () $($parsed)*] };
+    [! ($k:tt (lincomb) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (lincomb) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (ring) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (ring) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (panel $m:ident $($ps:tt)*) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (panel $m $($ps)*) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (rot $l:tt $r:ident $($rs:tt)*) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (rot $l $r $($rs)*) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt (tile $s:ident $e:ident) $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![& (tile $s $e) $y () ($($parsed)*); $assign $o ($($parsed)*)]
+    };
+    [! ($k:tt $m:tt $y:ident $assign:tt $o:tt) ($($parsed:tt)+) + .] => {
+        axpy![#+ (($k $m $y $assign $o car) $y car (
+            s0 s1 s2 s3 s4 s5 s6 s7 s8 s9 s10 s11 s12 s13 s14 s15 s16 s17 s18 s19 s20 s21 s22 s23 s24 s25 s26 s27 s28 s29 s30 s31 s32 s33 s34 s35 s36 s37 s38 s39 s40 s41 s42 s43 s44 s45 s46 s47 s48 s49 s50 s51 s52 s53 s54 s55 s56 s57 s58 s59 s60 s61 s62 s63
+        ) () () () ($($parsed)*))]
+    };
+    // (the common terms `± x` and `± a*x`, followed by another term or the end, are taken in a
+    // single step, as they would be in the three above)
+    [! $h:tt ($($parsed:tt)*) + $a:tt * $x:ident + $($rest:tt)+] => { axpy![! $h ($($parsed)* $a * $x ()) + $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) + $a:tt * $x:ident - $($rest:tt)+] => { axpy![! $h ($($parsed)* $a * $x ()) - $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) - $a:tt * $x:ident + $($rest:tt)+] => { axpy![! $h ($($parsed)* (-$a) * $x ()) + $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) - $a:tt * $x:ident - $($rest:tt)+] => { axpy![! $h ($($parsed)* (-$a) * $x ()) - $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) + $x:ident + $($rest:tt)+] => { axpy![! $h ($($parsed)* 0 + $x ()) + $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) + $x:ident - $($rest:tt)+] => { axpy![! $h ($($parsed)* 0 + $x ()) - $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) - $x:ident + $($rest:tt)+] => { axpy![! $h ($($parsed)* 0 - $x ()) + $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) - $x:ident - $($rest:tt)+] => { axpy![! $h ($($parsed)* 0 - $x ()) - $($rest)*] };
+    [! $h:tt () $a:tt * $x:ident + $($rest:tt)+] => { axpy![! $h ($a * $x ()) + $($rest)*] };
+    [! $h:tt () $a:tt * $x:ident - $($rest:tt)+] => { axpy![! $h ($a * $x ()) - $($rest)*] };
+    [! $h:tt () $x:ident + $($rest:tt)+] => { axpy![! $h (0 + $x ()) + $($rest)*] };
+    [! $h:tt () $x:ident - $($rest:tt)+] => { axpy![! $h (0 + $x ()) - $($rest)*] };
+    // ... which is preceded by its sign (optional for the leading term of each argument)
+    [! $h:tt ($($parsed:tt)*) , + $($rest:tt)+] => { axpy![! $h ($($parsed)* ,) [+] $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) , - $($rest:tt)+] => { axpy![! $h ($($parsed)* ,) [-] $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) , $($rest:tt)+] => { axpy![! $h ($($parsed)* ,) [+] $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) + $($rest:tt)+] => { axpy![! $h ($($parsed)*) [+] $($rest)*] };
+    [! $h:tt ($($parsed:tt)*) - $($rest:tt)+] => { axpy![! $h ($($parsed)*) [-] $($rest)*] };
+    [! $h:tt () $($rest:tt)+] => { axpy![! $h () [+] $($rest)*] };
+    // two operands in a row, most likely a coefficient missing its `*`
+    [! $h:tt $p:tt $t:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy!: expected `+`, `-` or `*` before `", stringify!($t), "`")) };
 
 
-    // within the linear combination expression, we need to replace each vector
-    // with the correct combination of obj.1. ... .1.0, e.g. peel back the zip()'s.
-
-    // Base case: when done, emit new expression
-    [# $y:ident; $car:ident; $cdr:expr; (+ $($parsed:tt)+)] => { $($parsed)* };
+    // lowering: every vector in the expression is replaced by the matching element of the loop,
+    // i.e. car (the mutably borrowed element of y) when x = y, or otherwise the element of x among
+    // the zipped sources, which are bound to s0, s1, ... (cdr being the list of the names not yet
+    // taken). Each distinct x is recorded as a source exactly once, together with its name, so
+    // that an operand appearing in several terms is read from a single stream.
+    // The code is accumulated as tokens throughout (rather than emitted by nested macro calls),
+    // so that it is available as a whole at the end.
 
-    // Case: + x
-    [# $y:ident; $car:ident; $cdr:expr; ($($parsed:tt)*) 0 + $x:ident $($rest:tt)*] => {
+    // The terms `± x` and `± a*x` of a plain operand x, by far the most common, take a shortcut
+    // (prefix=#+) that costs two steps of recursion if x is new: a single local macro, with an arm
+    // for y and for each source, chooses between the state for the target, for the rules below
+    // and for x recorded as a new source, all of which are built beforehand and passed to it
+    // whole (built inside its arms, the tokens would be marked as its own, and no longer refer to
+    // the loop's locals). The code of a new term travels with the state, and is appended on the
+    // way back in.
+    [#+ ($h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*) ($([$k:tt $n:ident])*) $done:tt ($($parsed:tt)*) (0 + $x:ident () $($rest:tt)*)) $($code:tt)*] => {
         {
             macro_rules! eval {
-                ($y $y) => { axpy![# $y; $car; $cdr  ; ($($parsed)* + *$car  ) $($rest)*] };
-                ($x $y) => { axpy![# $y; $car; $cdr.1; ($($parsed)* + *$cdr.0) $($rest)*] };
+                ($p:tt $q:tt $r:tt $y) => { axpy!$p };
+                $(($p:tt $q:tt $r:tt $k) => { axpy!$q };)*
+                ($p:tt $q:tt $r:tt $x) => { axpy!$r };
             }
-            eval!($x $y)
+            eval!([#found ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (+) ($($rest)*)) (*) () $car]
+                [#find ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (+) ($($rest)*)) [$x (*) ()]; ($([$k $n])*)]
+                [#+ ($h $y $car ($($ns)*) ($([$k $n])* [$x $n0]) $done ($($parsed)* $($code)*) ($($rest)*)) + (*$n0)] $x)
         }
     };
-    // Case: - x
-    [# $y:ident; $car:ident; $cdr:expr; ($($parsed:tt)*) 0 - $x:ident $($rest:tt)*] => {
+    [#+ ($h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*) ($([$k:tt $n:ident])*) $done:tt () (0 - $x:ident () $($rest:tt)*))] => {
         {
             macro_rules! eval {
-                ($y $y) => { axpy![# $y; $car; $cdr  ; ($($parsed)* + - *$car  ) $($rest)*] };
-                ($x $y) => { axpy![# $y; $car; $cdr.1; ($($parsed)* + - *$cdr.0) $($rest)*] };
+                ($p:tt $q:tt $r:tt $y) => { axpy!$p };
+                $(($p:tt $q:tt $r:tt $k) => { axpy!$q };)*
+                ($p:tt $q:tt $r:tt $x) => { axpy!$r };
             }
-            eval!($x $y)
+            eval!([#found ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done () (+ -) ($($rest)*)) (*) () $car]
+                [#find ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done () (+ -) ($($rest)*)) [$x (*) ()]; ($([$k $n])*)]
+                [#+ ($h $y $car ($($ns)*) ($([$k $n])* [$x $n0]) $done () ($($rest)*)) + - (*$n0)] $x)
+        }
+    };
+    [#+ ($h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*) ($([$k:tt $n:ident])*) $done:tt ($($parsed:tt)*) (0 - $x:ident () $($rest:tt)*)) $($code:tt)*] => {
+        {
+            macro_rules! eval {
+                ($p:tt $q:tt $r:tt $y) => { axpy!$p };
+                $(($p:tt $q:tt $r:tt $k) => { axpy!$q };)*
+                ($p:tt $q:tt $r:tt $x) => { axpy!$r };
+            }
+            eval!([#found ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (+ -) ($($rest)*)) (*) () $car]
+                [#find ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (+ -) ($($rest)*)) [$x (*) ()]; ($([$k $n])*)]
+                [#+ ($h $y $car ($($ns)*) ($([$k $n])* [$x $n0]) $done ($($parsed)* $($code)*) ($($rest)*)) - (*$n0)] $x)
+        }
+    };
+    [#+ ($h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*) ($([$k:tt $n:ident])*) $done:tt () ((-$a:tt) * $x:ident () $($rest:tt)*))] => {
+        {
+            macro_rules! eval {
+                ($p:tt $q:tt $r:tt $y $z:tt) =>
+                    { compile_error!(concat!("axpy!: the target `", stringify!($y), "` is used as a coefficient")) };
+                ($p:tt $q:tt $r:tt $a $y) => { axpy!$p };
+                $(($p:tt $q:tt $r:tt $a $k) => { axpy!$q };)*
+                ($p:tt $q:tt $r:tt $a $x) => { axpy!$r };
+            }
+            eval!([#found ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done () (* (-$a)) ($($rest)*)) (*) () $car]
+                [#find ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done () (* (-$a)) ($($rest)*)) [$x (*) ()]; ($([$k $n])*)]
+                [#+ ($h $y $car ($($ns)*) ($([$k $n])* [$x $n0]) $done () ($($rest)*)) + - $crate::__rt::Scale($crate::__rt::Term($a, (*$n0))).get()] $a $x)
+        }
+    };
+    [#+ ($h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*) ($([$k:tt $n:ident])*) $done:tt ($($parsed:tt)*) ((-$a:tt) * $x:ident () $($rest:tt)*)) $($code:tt)*] => {
+        {
+            macro_rules! eval {
+                ($p:tt $q:tt $r:tt $y $z:tt) =>
+                    { compile_error!(concat!("axpy!: the target `", stringify!($y), "` is used as a coefficient")) };
+                ($p:tt $q:tt $r:tt $a $y) => { axpy!$p };
+                $(($p:tt $q:tt $r:tt $a $k) => { axpy!$q };)*
+                ($p:tt $q:tt $r:tt $a $x) => { axpy!$r };
+            }
+            eval!([#found ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (* (-$a)) ($($rest)*)) (*) () $car]
+                [#find ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (* (-$a)) ($($rest)*)) [$x (*) ()]; ($([$k $n])*)]
+                [#+ ($h $y $car ($($ns)*) ($([$k $n])* [$x $n0]) $done ($($parsed)* $($code)*) ($($rest)*)) - $crate::__rt::Scale($crate::__rt::Term($a, (*$n0))).get()] $a $x)
         }
     };
-    // Case: + a * x
-    [# $y:ident; $car:ident; $cdr:expr; ($($parsed:tt)*) $a:tt * $x:ident $($rest:tt)*] => {
+    [#+ ($h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*) ($([$k:tt $n:ident])*) $done:tt ($($parsed:tt)*) ($a:tt * $x:ident () $($rest:tt)*)) $($code:tt)*] => {
         {
             macro_rules! eval {
-                ($y $y) => { axpy![# $y; $car; $cdr  ; ($($parsed)* + $a * *$car  ) $($rest)*] };
-                ($x $y) => { axpy![# $y; $car; $cdr.1; ($($parsed)* + $a * *$cdr.0) $($rest)*] };
+                ($p:tt $q:tt $r:tt $y $z:tt) =>
+                    { compile_error!(concat!("axpy!: the target `", stringify!($y), "` is used as a coefficient")) };
+                ($p:tt $q:tt $r:tt $a $y) => { axpy!$p };
+                $(($p:tt $q:tt $r:tt $a $k) => { axpy!$q };)*
+                ($p:tt $q:tt $r:tt $a $x) => { axpy!$r };
+            }
+            eval!([#found ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (* $a) ($($rest)*)) (*) () $car]
+                [#find ($h $y $car ($n0 $($ns)*) ($([$k $n])*) $done ($($parsed)* $($code)*) (* $a) ($($rest)*)) [$x (*) ()]; ($([$k $n])*)]
+                [#+ ($h $y $car ($($ns)*) ($([$k $n])* [$x $n0]) $done ($($parsed)* $($code)*) ($($rest)*)) + $crate::__rt::Scale($crate::__rt::Term($a, (*$n0))).get()] $a $x)
+        }
+    };
+    // (at the end, or at any other term, the state is unpacked again)
+    [#+ ($h:tt $y:ident $car:ident $cdr:tt $src:tt ($($done:tt)*) ($($parsed:tt)*) ()) $($code:tt)*] =>
+        { axpy![= $h; $src; $($done)* [$($parsed)* $($code)*]] };
+    [#+ ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt ($($parsed:tt)*) ($($rest:tt)*)) $($code:tt)*] =>
+        { axpy![# $h $y $car $cdr; $src; $done ($($parsed)* $($code)*) $($rest)*] };
+
+    // the target is a vector, so it cannot scale another term (this check has to precede the
+    // lowering rules below, which would otherwise attempt to match it)
+    [#coef $y:ident $a:tt; $($then:tt)*] => {
+        {
+            macro_rules! coef {
+                ($y) => { compile_error!(concat!("axpy!: the target `", stringify!($y), "` is used as a coefficient")) };
+                ((-$y)) => { compile_error!(concat!("axpy!: the target `", stringify!($y), "` is used as a coefficient")) };
+                ($a) => { $($then)* };
+            }
+            coef!($a)
+        }
+    };
+
+    // Base case: when done, hand the finished argument(s) to the outer function (prefix==)
+    [# $h:tt $y:ident $car:ident $cdr:tt; $src:tt; ($($done:tt)*) ($($parsed:tt)+)] =>
+        { axpy![= $h; $src; $($done)* [$($parsed)*]] };
+
+    // Case: , (end of an argument)
+    [# $h:tt $y:ident $car:ident $cdr:tt; $src:tt; ($($done:tt)*) ($($parsed:tt)+) , $($rest:tt)*] =>
+        { axpy![# $h $y $car $cdr; $src; ($($done)* [$($parsed)*]) () $($rest)*] };
+
+    // Cases: the shortcut above, when a name is left for a new source ...
+    [# $h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*); $src:tt; $done:tt $parsed:tt 0 $s:tt $x:ident () $($rest:tt)*] =>
+        { axpy![#+ ($h $y $car ($n0 $($ns)*) $src $done $parsed (0 $s $x () $($rest)*))] };
+    [# $h:tt $y:ident $car:ident ($n0:ident $($ns:tt)*); $src:tt; $done:tt $parsed:tt $a:tt * $x:ident () $($rest:tt)*] =>
+        { axpy![#+ ($h $y $car ($n0 $($ns)*) $src $done $parsed ($a * $x () $($rest)*))] };
+
+    // ... or else + x, - x, + a * x
+    [# $h:tt $y:ident $car:ident $cdr:tt; $src:tt; $done:tt $parsed:tt 0 + $x:ident $w:tt $($rest:tt)*] =>
+        { axpy![#term ($h $y $car $cdr $src $done $parsed (+) ($($rest)*)) $x $w] };
+    [# $h:tt $y:ident $car:ident $cdr:tt; $src:tt; $done:tt $parsed:tt 0 - $x:ident $w:tt $($rest:tt)*] =>
+        { axpy![#term ($h $y $car $cdr $src $done $parsed (+ -) ($($rest)*)) $x $w] };
+    [# $h:tt $y:ident $car:ident $cdr:tt; $src:tt; $done:tt $parsed:tt $a:tt * $x:ident $w:tt $($rest:tt)*] =>
+        { axpy![#coef $y $a; axpy![#term ($h $y $car $cdr $src $done $parsed (* $a) ($($rest)*)) $x $w]] };
+
+    // the element of x is car if x = y, or else found among the sources (prefix=#find) by a local
+    // macro with an arm for each, which yields the name of x, or the next name for a new, last
+    // source (prefix=#new). Slices are recorded as x and yield references, iterators as (iter x)
+    // and yield values. Columns, (col x j lda), macro invocations, (mac m args), and reversed
+    // slices, (rev x), are never merged with other sources.
+    [#term $st:tt $x:ident (iter $($w:tt)*)] => { axpy![#term $st $x [(iter $x) () ($($w)*)]] };
+    [#term $st:tt $x:ident (rev)] => { axpy![#new $st [(rev $x) (*) ()]] };
+    [#term $st:tt $x:ident (col $j:tt $lda:tt)] => { axpy![#new $st [(col $x $j $lda) (*) ()]] };
+    [#term $st:tt $m:ident (mac $a:tt)] => { axpy![#new $st [(mac $m $a) (*) ()]] };
+    [#term $st:tt $x:ident ($($w:tt)*)] => { axpy![#term $st $x [$x (*) ($($w)*)]] };
+    [#term ($h:tt $y:ident $car:ident $cdr:tt $src:tt $($st:tt)*) $x:ident [$k:tt $d:tt $w:tt]] => {
+        {
+            macro_rules! eval {
+                ($y $y) => { axpy![#found ($h $y $car $cdr $src $($st)*) (*) $w $car] };
+                ($x $y) => { axpy![#find ($h $y $car $cdr $src $($st)*) [$k $d $w]; $src] };
             }
             eval!($x $y)
         }
     };
+    [#find $st:tt [$k:tt $d:tt $w:tt]; ($([$s:tt $n:ident])*)] => {
+        {
+            macro_rules! eval {
+                $(($s) => { axpy![#found $st $d $w $n] };)*
+                ($k) => { axpy![#new $st [$k $d $w]] };
+            }
+            eval!($k)
+        }
+    };
+    [#new ($h:tt $y:ident $car:ident ($n:ident $($ns:tt)*) ($($src:tt)*) $($st:tt)*) [$k:tt $d:tt $w:tt]] =>
+        { axpy![#found ($h $y $car ($($ns)*) ($($src)* [$k $n]) $($st)*) $d $w $n] };
+    [#new $st:tt $k:tt] => { compile_error!("axpy!: too many distinct operands (at most 64)") };
+    // (a divided term is __rt::Quot, which multiplies floats by the reciprocal; the division
+    // comes after the scaling, as in `(a * x) / d`)
+    [#found ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt $parsed:tt (* (-$a:tt)) $rest:tt) ($($d:tt)*) (div $q:tt) $e:expr] => {
+        axpy![#found ($h $y $car $cdr $src $done $parsed (+ -) $rest) () ()
+            ($crate::__rt::Quot($crate::__rt::Ratio($crate::__rt::Scale($crate::__rt::Term($a, ($($d)*$e))).get(), $q)).get())]
+    };
+    [#found ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt $parsed:tt (* $a:tt) $rest:tt) ($($d:tt)*) (div $q:tt) $e:expr] => {
+        axpy![#found ($h $y $car $cdr $src $done $parsed (+) $rest) () ()
+            ($crate::__rt::Quot($crate::__rt::Ratio($crate::__rt::Scale($crate::__rt::Term($a, ($($d)*$e))).get(), $q)).get())]
+    };
+    [#found $st:tt ($($d:tt)*) (div $q:tt) $e:expr] =>
+        { axpy![#found $st () () ($crate::__rt::Quot($crate::__rt::Ratio(($($d)*$e), $q)).get())] };
+    // (a scaled element is a * x, unless x is Wrapping or Saturating, see __rt::Scale; terms are
+    // emitted with their sign folded in, e.g. `- x` and `- 2.*x` rather than `+ -x` and `+ (-2.)*x`,
+    // so that only a leading term is ever negated)
+    [#found ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt ($($parsed:tt)+) (* (-$a:tt)) ($($rest:tt)*)) ($($d:tt)*) ($($w:tt)*) $e:expr] =>
+        { axpy![#+ ($h $y $car $cdr $src $done ($($parsed)*) ($($rest)*)) - $crate::__rt::Scale($crate::__rt::Term($a, ($($d)*$e)$($w)*)).get()] };
+    [#found ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt () (* (-$a:tt)) ($($rest:tt)*)) ($($d:tt)*) ($($w:tt)*) $e:expr] =>
+        { axpy![#+ ($h $y $car $cdr $src $done () ($($rest)*)) + - $crate::__rt::Scale($crate::__rt::Term($a, ($($d)*$e)$($w)*)).get()] };
+    [#found ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt ($($parsed:tt)*) (* $a:tt) ($($rest:tt)*)) ($($d:tt)*) ($($w:tt)*) $e:expr] =>
+        { axpy![#+ ($h $y $car $cdr $src $done ($($parsed)*) ($($rest)*)) + $crate::__rt::Scale($crate::__rt::Term($a, ($($d)*$e)$($w)*)).get()] };
+    // (a cast is parenthesized, as `as` binds looser than unary minus)
+    [#found $st:tt ($($d:tt)*) (as $t:ident) $e:expr] => { axpy![#found $st () () (($($d)*$e) as $t)] };
+    [#found ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt ($($parsed:tt)+) (+ -) ($($rest:tt)*)) ($($d:tt)*) ($($w:tt)*) $e:expr] =>
+        { axpy![#+ ($h $y $car $cdr $src $done ($($parsed)*) ($($rest)*)) - ($($d)*$e)$($w)*] };
+    [#found ($h:tt $y:ident $car:ident $cdr:tt $src:tt $done:tt ($($parsed:tt)*) ($($op:tt)*) ($($rest:tt)*)) ($($d:tt)*) ($($w:tt)*) $e:expr] =>
+        { axpy![#+ ($h $y $car $cdr $src $done ($($parsed)*) ($($rest)*)) $($op)* ($($d)*$e)$($w)*] };
+
+    // outer function application, folding min/max from the left
+    [= ($k:tt $m:tt $y:ident $assign:tt () $v:tt); $src:tt; [+ $($e:tt)+]] =>
+        { axpy![@ ($k $m $y $assign $v); ($($e)+); () $src] };
+    [= ($k:tt $m:tt $y:ident $assign:tt (clamp $lo:tt $hi:tt) $v:tt); $src:tt; [+ $($e:tt)+]] =>
+        { axpy![@ ($k $m $y $assign $v); ($crate::__rt::clamp($($e)+, $lo, $hi)); () $src] };
+    [= ($k:tt $m:tt $y:ident $assign:tt (cmp $op:tt $t:tt) $v:tt); $src:tt; [+ $($e:tt)+]] =>
+        { axpy![@ ($k $m $y $assign $v); (($($e)+) $op $t); () $src] };
+    [= ($k:tt $m:tt $y:ident $assign:tt (cmp $op:tt) $v:tt); $src:tt; [+ $($a:tt)+] [+ $($b:tt)+]] =>
+        { axpy![@ ($k $m $y $assign $v); (($($a)+) $op ($($b)+)); () $src] };
+    [= ($k:tt $m:tt $y:ident $assign:tt (abs) $v:tt); $src:tt; [+ $($e:tt)+]] =>
+        { axpy![@ ($k $m $y $assign $v); ($crate::__rt::abs($($e)+)); () $src] };
+    [= ($k:tt $m:tt $y:ident $assign:tt ($f:ident) $v:tt); $src:tt; [+ $($e:tt)+]] =>
+        { axpy![@ ($k $m $y $assign $v); ($($e)+); () $src] };
+    [= $h:tt; $src:tt; [+ $($a:tt)+] [+ $($b:tt)+] $($rest:tt)*] => {
+        axpy![= $h; $src; [+ axpy![=f $h] ($($a)+, $($b)+)] $($rest)*]
+    };
+    [=f ($k:tt $m:tt $y:ident $assign:tt ($f:ident) $v:tt)] => { $crate::__rt::$f };
+
+
+    // iterator construction: the sources are zipped pairwise into a balanced tree, and the nested
+    // pairs are destructured right away by a pattern of the same shape, which binds s0, s1, ...
+    // as flat locals of the loop body. With a length policy, strict: or checked:, the lengths of
+    // the slice operands (the sources recorded by name) are compared first (prefix=@len), and
+    // with ptr(n): their address ranges are checked against the target's in debug builds, after
+    // which the mode becomes (raw n) ...
+    [@ ($k:tt (ptr $p:ident) $y:ident $assign:tt $v:tt); $e:tt; () ($([$x:ident $n:ident])*)] =>
+        { axpy![@len alias ($k $y $assign $v $p); $e; ($($x)*) () ($([$x $n])*)] };
+    [@ ($k:tt (ptr $p:ident) $y:ident $assign:tt $v:tt); $e:tt; () $src:tt] =>
+        { axpy![@len alias ($k $y $assign $v $p); $e; () $src $src] };
+    [@ ($k:tt (len $p:ident) $y:ident $assign:tt $v:tt); $e:tt; () ($([$x:ident $n:ident])*)] =>
+        { axpy![@len $p ($k $y $assign $v); $e; ($($x)*) () ($([$x $n])*)] };
+    [@ ($k:tt (len $p:ident) $y:ident $assign:tt $v:tt); $e:tt; () $src:tt] => { axpy![@len $p ($k $y $assign $v); $e; () $src $src] };
+    [@len $p:ident $h:tt; $e:tt; ($($u:ident)*) ([$x:ident $n:ident] $($more:tt)*) $src:tt] =>
+        { axpy![@len $p $h; $e; ($($u)* $x) ($($more)*) $src] };
+    [@len $p:ident $h:tt; $e:tt; $u:tt ($s:tt $($more:tt)*) $src:tt] => { axpy![@len $p $h; $e; $u ($($more)*) $src] };
+    [@len alias ($k:tt $y:ident $assign:tt $v:tt $p:ident); $e:tt; ($($u:ident)*) () $src:tt] => {{
+        if cfg!(debug_assertions) {
+            $crate::__rt::no_alias(stringify!($y), $crate::__rt::span($y, $p),
+                &[$((stringify!($u), $crate::__rt::span($u, $p))),*]);
+        }
+        axpy![@ ($k (raw $p) $y $assign $v); $e; () $src]
+    }};
+    [@len $p:ident ($k:tt $y:ident $assign:tt $v:tt); $e:tt; ($($u:ident)*) () $src:tt] => {
+        axpy![&len $p;
+            $crate::__rt::same_len(stringify!($y), $y.len(), &[$((stringify!($u), $u.len())),*]);
+            axpy![@ ($k () $y $assign $v); $e; () $src]]
+    };
+    // ... then each source is replaced by its iterator as appropriate for the mode (prefix=@),
+    // all at once if they are all slices ...
+    [@ ($k:tt (raw $p:ident) $y:ident $assign:tt $v:tt); $e:tt; () ($([$x:ident $n:ident])*)] =>
+        { axpy![^ ($k (raw $p) $y $assign $v); $e; $([[$crate::__rt::from_raw_parts($x, $p).iter()] $n])*] };
+    [@ $h:tt; $e:tt; () ($([$x:ident $n:ident])*)] => { axpy![^ $h; $e; $([[$x.iter()] $n])*] };
+    // ... or else one at a time
+    [@ $h:tt; $e:tt; ($($its:tt)*) ([(iter $x:ident) $n:ident] $($src:tt)*)] =>
+        { axpy![@ $h; $e; ($($its)* [[$x] $n]) ($($src)*)] };
+    [@ $h:tt; $e:tt; ($($its:tt)*) ([(rev $x:ident) $n:ident] $($src:tt)*)] =>
+        { axpy![@ $h; $e; ($($its)* [[$x.iter().rev()] $n]) ($($src)*)] };
+    [@ $h:tt; $e:tt; ($($its:tt)*) ([(mac $m:ident $a:tt) $n:ident] $($src:tt)*)] =>
+        { axpy![@ $h; $e; ($($its)* [[$m!$a.iter()] $n]) ($($src)*)] };
+    [@ $h:tt; $e:tt; ($($its:tt)*) ([(col $x:ident ($j:expr) ($lda:expr)) $n:ident] $($src:tt)*)] =>
+        { axpy![@ $h; $e; ($($its)* [[$x[$j..].iter().step_by($lda)] $n]) ($($src)*)] };
+    [@ ($k:tt (raw $p:ident) $y:ident $assign:tt $v:tt); $e:tt; ($($its:tt)*) ([$x:ident $n:ident] $($src:tt)*)] =>
+        { axpy![@ ($k (raw $p) $y $assign $v); $e; ($($its)* [[$crate::__rt::from_raw_parts($x, $p).iter()] $n]) ($($src)*)] };
+    [@ $h:tt; $e:tt; ($($its:tt)*) ([$x:ident $n:ident] $($src:tt)*)] =>
+        { axpy![@ $h; $e; ($($its)* [[$x.iter()] $n]) ($($src)*)] };
+    [@ $h:tt; $e:tt; ($($its:tt)*) ()] => { axpy![^ $h; $e; $($its)*] };
+    // ... and then pair up neighbours, chain and pattern alike, until one is left (prefix=^),
+    // which takes as many steps as the tree is deep
+    [^ ($k:tt $m:tt $y:ident $assign:tt $car:ident); $e:tt;] => { axpy![% $k $m $y $assign ($car ()) $e ()] };
+    [^ ($k:tt $m:tt $y:ident $assign:tt $car:ident); $e:tt; [[$($it:tt)*] $pat:tt]] =>
+        { axpy![% $k $m $y $assign ($car $pat) $e ($($it)*)] };
+    [^ $h:tt; $e:tt; $([[$($a:tt)*] $p:tt] [[$($b:tt)*] $q:tt])+] =>
+        { axpy![^ $h; $e; $([[$($a)*.zip($($b)*)] ($p, $q)])+] };
+    [^ $h:tt; $e:tt; $first:tt $([[$($a:tt)*] $p:tt] [[$($b:tt)*] $q:tt])+] =>
+        { axpy![^ $h; $e; $first $([[$($a)*.zip($($b)*)] ($p, $q)])+] };
+
+    // the statement itself: the target is iterated as appropriate for the mode (prefix=%), and
+    // enumerated if the loop index is used (prefix=%%); the sources chain may be empty
+    [% $k:tt () $y:ident $assign:tt $v:tt $e:tt $chain:tt] =>
+        { axpy![%% $k ($y.iter_mut()) $assign $v $e $chain] };
+    [% $k:tt (col ($j:expr) ($lda:expr)) $y:ident $assign:tt $v:tt $e:tt $chain:tt] =>
+        { axpy![%% $k ($y[$j..].iter_mut().step_by($lda)) $assign $v $e $chain] };
+    [% $k:tt (raw $n:ident) $y:ident $assign:tt $v:tt $e:tt $chain:tt] =>
+        { axpy![%% $k ($crate::__rt::from_raw_parts_mut($y, $n).iter_mut()) $assign $v $e $chain] };
+    [% $k:tt (new) $y:ident $assign:tt ($car:ident $cdr:tt) ($($e:tt)*) ($($chain:tt)+)] => { axpy![? $k;
+        $($chain)+.map(|$cdr| $($e)*).collect::<$crate::__rt::Vec<_>>()
+    ] };
+    [% $k:tt (fold $f:ident) $y:ident $assign:tt ($car:ident $cdr:tt) ($($e:tt)*) ($($chain:tt)+)] =>
+        { axpy![? $k; $crate::__rt::$f($($chain)+.map(|$cdr| $($e)*))] };
+    [% $k:tt (sink $f:ident $s:ident) $y:ident $assign:tt ($car:ident $cdr:tt) ($($e:tt)*) ($($chain:tt)+)] =>
+        { axpy![? $k; $crate::__rt::$f($s, $($chain)+.map(|$cdr| $($e)*))] };
+    [%% ($k:ident $i:ident) ($($t:tt)*) $assign:tt ($car:ident $cdr:tt) $e:tt $chain:tt] =>
+        { axpy![%% $k ($($t)*.enumerate()) $assign (($i, $car) $car $cdr) $e $chain] };
+    [%% $k:ident $t:tt $assign:tt ($car:ident $cdr:tt) $e:tt $chain:tt] =>
+        { axpy![%% $k $t $assign ($car $car $cdr) $e $chain] };
+    [%% $k:ident ($($t:tt)*) $assign:tt ($pat:tt $car:ident $cdr:tt) ($($e:tt)*) ()] => { axpy![? $k;
+        for $pat in $($t)* {
+            *$car $assign $($e)*;
+        }
+    ] };
+    [%% $k:ident ($($t:tt)*) $assign:tt ($pat:tt $car:ident $cdr:tt) ($($e:tt)*) ($($chain:tt)+)] => { axpy![? $k;
+        for ($pat, $cdr) in $($t)*.zip($($chain)+) {
+            *$car $assign $($e)*;
+        }
+    ] };
+
+    // finally, emit the code (or also return it as a string), with the operand traits in scope
+    [? emit; $($code:tt)*] => {
+        {
+            #[allow(unused_imports)]
+            use $crate::{AxpySource as _, AxpyTarget as _};
+            $($code)*
+        }
+    };
+    [? expand; $($code:tt)*] => {
+        {
+            #[allow(unused_imports)]
+            use $crate::{AxpySource as _, AxpyTarget as _};
+            $($code)*;
+            stringify!($($code)*)
+        }
+    };
+
+
+    // tiled evaluation of several statements: split them at `;` (prefix=;tile), then run every
+    // statement on the first b elements, then every statement on the next b, and so on
+    // (with tile(auto):, the tuner is told the time taken once done)
+    [;tile $b:expr; $f:tt; $h:tt; ($($st:tt)*) () ; $($rest:tt)*] => { axpy![;tile $b; $f; $h; ($($st)*) () $($rest)*] };
+    [;tile $b:expr; $f:tt; $h:tt; ($($st:tt)*) ($($cur:tt)+) ; $($rest:tt)*] =>
+        { axpy![;tile $b; $f; $h; ($($st)* [$($cur)*]) () $($rest)*] };
+    [;tile $b:expr; $f:tt; $h:tt; $st:tt ($($cur:tt)*) $t:tt $($rest:tt)*] =>
+        { axpy![;tile $b; $f; $h; $st ($($cur)* $t) $($rest)*] };
+    [;tile $b:expr; $f:tt; $h:tt; ($([$y:ident $assign:tt $($stmt:tt)+])+) ()] => {
+        {
+            let b: usize = $b;
+            assert!(b > 0, "axpy!: the tile size must be positive");
+            let mut n = 0;
+            $( n = $crate::__rt::max(n, $y.len()); )+
+            let mut s = 0;
+            while s < n {
+                let e = $crate::__rt::min(n, s.saturating_add(b));
+                axpy![;hook $h s e { $( axpy![! (emit (tile s e) $y $assign ()) () $($stmt)* + .]; )+ }];
+                s = e;
+            }
+            axpy![;tune $f n];
+        }
+    };
+
+    [;tune () $n:ident] => {};
+    [;tune ($tuner:ident $probe:ident) $n:ident] => { $tuner.finish($probe, $n) };
+
+    // stencils: replace every shifted operand x(k) by an invocation of the local macro shift!(x (k))
+    // (prefix=;shift), recording the shifts; then run the statement on the interior rows and on
+    // every edge row, with every shifted operand bound beforehand (prefix=;bind, the bindings
+    // being told apart by hygiene) to x cut to the rows moved by k, or to x's element picked by
+    // the policy, and shift! mapping each (x (k)) to its binding
+    // (with the shift kept as a plain token, so that shift!'s patterns can match it)
+    [;shift $s:ident $sh:tt $k:tt ($($acc:tt)*) $f:ident ($x:ident) $($rest:tt)*] =>
+        { axpy![;shift $s $sh $k ($($acc)* $f ($x)) $($rest)*] };
+    [;shift $s:ident ($($sh:tt)*) $k:tt ($($acc:tt)*) $x:ident (+ $n:tt) $($rest:tt)*] =>
+        { axpy![;shift $s ($($sh)* [$x ($n)]) $k ($($acc)* $s!($x ($n))) $($rest)*] };
+    [;shift $s:ident ($($sh:tt)*) $k:tt ($($acc:tt)*) $x:ident (- $n:tt) $($rest:tt)*] =>
+        { axpy![;shift $s ($($sh)* [$x (-$n)]) $k ($($acc)* $s!($x (-$n))) $($rest)*] };
+    [;shift $s:ident ($($sh:tt)*) $k:tt ($($acc:tt)*) $x:ident ($n:tt) $($rest:tt)*] =>
+        { axpy![;shift $s ($($sh)* [$x ($n)]) $k ($($acc)* $s!($x ($n))) $($rest)*] };
+    [;shift $s:ident $sh:tt $k:tt ($($acc:tt)*) $t:tt $($rest:tt)*] => { axpy![;shift $s $sh $k ($($acc)* $t) $($rest)*] };
+    [;shift $s:ident ($([$x:ident $n:tt])*) ($p:ident $y:ident $assign:tt) ($($acc:tt)*)] => {
+        {
+            let mut n = $y.len();
+            $( n = $crate::__rt::min(n, $x.len()); )*
+            // the interior rows lo..hi, where every shift is in range
+            let (mut lo, mut hi) = (0, n);
+            $(
+                lo = $crate::__rt::max(lo, ($n as isize).min(0).unsigned_abs());
+                hi = $crate::__rt::min(hi, ($x.len() as isize - $n).max(0) as usize);
+            )*
+            let lo = $crate::__rt::min(lo, n);
+            let hi = $crate::__rt::max(lo, hi);
+            if lo < hi {
+                axpy![;bind $s (cut lo hi) $y $assign ($($acc)*) () $([$x $n])*]
+            }
+            axpy![;edges $p $s (lo hi n) $y $assign ($($acc)*) $([$x $n])*];
+        }
+    };
+    [;edges skip $($rest:tt)*] => {};
+    [;edges clamp $($rest:tt)*] => { axpy![;edges (clamped) $($rest)*] };
+    [;edges wrap $($rest:tt)*] => { axpy![;edges (wrapped) $($rest)*] };
+    [;edges ($f:ident) $s:ident ($lo:ident $hi:ident $n:ident) $y:ident $assign:tt $acc:tt $($sh:tt)*] => {
+        for i in (0..$lo).chain($hi..$n) {
+            axpy![;bind $s (pick $f i) $y $assign $acc () $($sh)*];
+        }
+    };
+    [;edges $p:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy!: stencil: expected `skip`, `clamp` or `wrap`, found `", stringify!($p), "`")) };
+    [;bind $s:ident $c:tt $y:ident $assign:tt $acc:tt ($($done:tt)*) [$x:ident $n:tt] $($more:tt)*] => {
+        {
+            let shifted = axpy![;bind $c $x $n];
+            axpy![;bind $s $c $y $assign $acc ($($done)* [$x $n shifted]) $($more)*];
+        }
+    };
+    [;bind (cut $lo:ident $hi:ident) $x:ident $n:tt] =>
+        { &$x[($lo as isize + $n) as usize..($hi as isize + $n) as usize] };
+    [;bind (pick $f:ident $i:ident) $x:ident $n:tt] =>
+        { $crate::__rt::from_ref(&$x[$crate::__rt::$f($i, $n as isize, $x.len())]) };
+    [;bind $s:ident (cut $lo:ident $hi:ident) $y:ident $assign:tt ($($acc:tt)*) ($([$x:ident $n:tt $b:ident])*)] => {
+        macro_rules! $s { $( ($x $n) => { $b }; )* }
+        axpy![! (emit (tile $lo $hi) $y $assign ()) () $($acc)* + .];
+    };
+    [;bind $s:ident (pick $f:ident $i:ident) $y:ident $assign:tt ($($acc:tt)*) ($([$x:ident $n:tt $b:ident])*)] => {
+        macro_rules! $s { $( ($x $n) => { $b }; )* }
+        let e = $i + 1;
+        axpy![! (emit (tile $i e) $y $assign ()) () $($acc)* + .];
+    };
+
+    // a block with temporaries: split it at `;` (prefix=;block) and parse every statement in
+    // turn (prefix=;parse), recording the temporaries and the distinct targets ...
+    [;block $w:expr; $h:tt; ($($st:tt)*) () ; $($rest:tt)*] => { axpy![;block $w; $h; ($($st)*) () $($rest)*] };
+    [;block $w:expr; $h:tt; ($($st:tt)*) ($($cur:tt)+) ; $($rest:tt)*] =>
+        { axpy![;block $w; $h; ($($st)* [$($cur)*]) () $($rest)*] };
+    [;block $w:expr; $h:tt; $st:tt ($($cur:tt)*) $t:tt $($rest:tt)*] => { axpy![;block $w; $h; $st ($($cur)* $t) $($rest)*] };
+    [;block $w:expr; $h:tt; ($($st:tt)*) ()] => { axpy![;parse ($w; $h) () () () $($st)*] };
+    [;parse $w:tt $t:tt $z:tt $done:tt [let $y:ident = $($e:tt)+] $($more:tt)*] =>
+        { axpy![! (emit (block let $w $t $z $done ($($more)*)) $y = ()) () $($e)* + .] };
+    [;parse $w:tt $t:tt $z:tt $done:tt [$y:ident $assign:tt $($e:tt)+] $($more:tt)*] =>
+        { axpy![! (emit (block set $w $t $z $done ($($more)*)) $y $assign ()) () $($e)* + .] };
+    [;parse $w:tt $t:tt $z:tt $done:tt [$($st:tt)*] $($more:tt)*] =>
+        { compile_error!(concat!("axpy!: expected `let t = ...` or an assignment, found `", stringify!($($st)*), "`")) };
+    [;parse $w:tt $t:tt () $done:tt] => { compile_error!("axpy!: a block with temporaries assigns nothing") };
+    // ... and then collect the names of all of them, and evaluate the statements chunk by chunk,
+    // the temporaries being stack arrays of a chunk's length
+    [;parse $w:tt ($($t:ident)*) ($z0:ident $($z:ident)*) ($([$d:ident $assign:tt $o:tt ($($parsed:tt)*)])*)] => {
+        axpy![& (block) $z0 ($($t)* $z0 $($z)*) ($($($parsed)* ,)*);
+            $w ($($t)*) ($z0 $($z)*) ($([$d $assign $o ($($parsed)*)])*)]
+    };
+
+    // row-wise and tiled evaluation: collect the distinct source operands, then rebind every name
+    // to its current row (or tile) so that the unchanged statement can be evaluated on each
+    [&pat $x:ident] => { ($x,) };
+    [&pat $x:ident $($rest:ident)+] => { ($x, axpy![&pat $($rest)+]) };
+    [&zip $x:ident] => { $x.iter().map(|x| (x,)) };
+    [&zip $x:ident $($rest:ident)+] => { $x.iter().zip(axpy![&zip $($rest)+]) };
+    [& (rows) $y:ident () (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        for $y in $y.iter_mut() {
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    [& (rows) $y:ident ($($u:ident)+) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        for ($y, axpy![&pat $($u)+]) in $y.iter_mut().zip(axpy![&zip $($u)+]) {
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    // (rows of length n of flat buffers, and the same split into a block of rows per job)
+    [&zip $n:ident; $x:ident] => { $x.chunks($n).map(|x| (x,)) };
+    [&zip $n:ident; $x:ident $($rest:ident)+] => { $x.chunks($n).zip(axpy![&zip $n; $($rest)+]) };
+    [& (rows $n:ident) $y:ident () (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        for $y in $y.chunks_mut($n) {
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    [& (rows $n:ident) $y:ident ($($u:ident)+) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        for ($y, axpy![&pat $($u)+]) in $y.chunks_mut($n).zip(axpy![&zip $n; $($u)+]) {
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    [& (par $x:ident $h:tt $($n:ident)?) $y:ident ($($u:ident)*) (); $($stmt:tt)*] => {
+        {
+            let parts = $crate::exec::Parts::new(&mut $y[..], $crate::exec::Executor::jobs($x), axpy![&row $($n)?]);
+            $crate::exec::Executor::run($x, parts.count(), |k| {
+                // (the executor takes every part at most once)
+                let (s, $y) = unsafe { parts.part(k) };
+                let e = s + $y.len();
+                axpy![;hook $h s e {
+                    $( let $u = $crate::__rt::tile(&$u[..], s, e); )*
+                    axpy![&par $y ($($u)*) $($n)?; $($stmt)*]
+                }]
+            });
+        }
+    };
+    [&row] => { 1 };
+    [&row $n:ident] => { $n };
+    [&par $y:ident $u:tt; $assign:tt $o:tt ($($parsed:tt)*)] => { axpy![! (emit () $y $assign $o) ($($parsed)*) + .] };
+    [&par $y:ident ($($u:ident)*) $n:ident; $($stmt:tt)*] => { axpy![& (rows $n) $y ($($u)*) (); $($stmt)*] };
+    [& (tile $s:ident $e:ident) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            let $y = $crate::__rt::tile_mut(&mut $y[..], $s, $e);
+            $( let $u = $crate::__rt::tile(&$u[..], $s, $e); )*
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    // rotated operands: every rot(x, k) splits the rows in two, those before the wraparound reading
+    // x from k on and those after it reading x from its start, so the rows are split at every
+    // wraparound (prefix=;rot), and the statement evaluated on each segment with every rotated
+    // operand bound to the matching slice of x
+    // (with a length policy, the rotated operands are compared with the rest first)
+    [& (rot $l:tt $r:ident $([$x:ident $n:tt])*) $y:ident ($($u:ident)*) (); $($stmt:tt)*] => {
+        axpy![;rot $l;
+            $crate::__rt::same_len(stringify!($y), $y.len(),
+                &[$((stringify!($u), $u.len()),)* $((stringify!($x), $x.len()),)*]);
+            {
+                let mut n = $y.len();
+                $( n = $crate::__rt::min(n, $u.len()); )*
+                $( n = $crate::__rt::min(n, $x.len()); )*
+                let s = 0;
+                axpy![;rot $r (s n) ($([$x $n])*) () ($([$x $n])*); $y $($stmt)*];
+            }]
+    };
+    [;rot (); $c:expr; $($stmt:tt)*] => { $($stmt)* };
+    [;rot (len $p:ident); $($rest:tt)*] => { axpy![&len $p; $($rest)*] };
+    [;rot $r:ident ($s:ident $e:ident) ([$x:ident $n:tt] $($more:tt)*) () $all:tt; $($stmt:tt)*] => {
+        {
+            let m = $x.len();
+            let p = $crate::__rt::min($e, $crate::__rt::max($s, m - $crate::__rt::rotation($n as isize, m)));
+            if $s < p {
+                axpy![;rot $r ($s p) ($($more)*) () $all; $($stmt)*];
+            }
+            if p < $e {
+                axpy![;rot $r (p $e) ($($more)*) () $all; $($stmt)*];
+            }
+        }
+    };
+    [;rot $r:ident ($s:ident $e:ident) () ($($done:tt)*) ([$x:ident $n:tt] $($more:tt)*); $($stmt:tt)*] => {
+        {
+            let rotated = $crate::__rt::rotated(&$x[..], $n as isize, $s, $e);
+            axpy![;rot $r ($s $e) () ($($done)* [$x $n rotated]) ($($more)*); $($stmt)*];
+        }
+    };
+    [;rot $r:ident ($s:ident $e:ident) () ($([$x:ident $n:tt $b:ident])*) (); $y:ident $assign:tt $o:tt $parsed:tt] => {
+        macro_rules! $r { $( ($x $n) => { $b }; )* }
+        axpy![! (emit (tile $s $e) $y $assign $o) $parsed + .];
+    };
+    // panels: the statement on every row, i.e. on the target's row, on every panel operand's row
+    // (prefix=;panel, each bound in turn and mapped to by the local macro) and on the matching
+    // rows of the other slices, read as packed panels of the same number of columns
+    [& (panel $m:ident ($r:expr) ($c:expr) ($ld:expr) $([$x:ident $a:tt])*) $y:ident ($($u:ident)*) (); $($stmt:tt)*] => {
+        {
+            let (mut rows, mut cols, ld): (usize, usize, usize) = ($r, $c, $ld);
+            $(
+                rows = $crate::__rt::min(rows, axpy![;panel rows $a]);
+                cols = $crate::__rt::min(cols, axpy![;panel cols $a]);
+            )*
+            for i in 0..rows {
+                axpy![;panel $m (i cols ld) () ($([$x $a])*); $y ($($u)*) $($stmt)*];
+            }
+        }
+    };
+    [;panel rows ($r:expr, $c:expr, $ld:expr)] => { $r };
+    [;panel cols ($r:expr, $c:expr, $ld:expr)] => { $c };
+    [;panel row $x:ident $i:ident $n:ident ($r:expr, $c:expr, $ld:expr)] =>
+        { $crate::__rt::panel_row(&$x[..], $i, $n, $ld) };
+    [;panel $m:ident ($i:ident $n:ident $ld:ident) ($($done:tt)*) ([$x:ident $a:tt] $($more:tt)*); $($stmt:tt)*] => {
+        {
+            let row = axpy![;panel row $x $i $n $a];
+            axpy![;panel $m ($i $n $ld) ($($done)* [$x $a row]) ($($more)*); $($stmt)*];
+        }
+    };
+    [;panel $m:ident ($i:ident $n:ident $ld:ident) ($([$x:ident $a:tt $b:ident])*) (); $y:ident ($($u:ident)*) $assign:tt $o:tt $parsed:tt] => {
+        macro_rules! $m { $( ($x $a) => { $b }; )* }
+        let $y = $crate::__rt::panel_row_mut(&mut $y[..], $i, $n, $ld);
+        $( let $u = $crate::__rt::panel_row(&$u[..], $i, $n, $n); )*
+        axpy![! (emit () $y $assign $o) $parsed + .];
+    };
+    [& (upto $n:ident $k:tt) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            let $y = &mut $y[..$n];
+            $( let $u = &$u[..$n]; )*
+            axpy![! ($k () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    // (explicit vector width: the whole chunks of w elements, then the remainder as requested)
+    [&zip exact $w:ident; $x:ident] => { $x.chunks_exact($w).map(|x| (x,)) };
+    [&zip exact $w:ident; $x:ident $($rest:ident)+] => { $x.chunks_exact($w).zip(axpy![&zip exact $w; $($rest)+]) };
+    [& (tail $t:ident ($w:expr)) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            const W: usize = $w;
+            let mut n = $y.len();
+            $( n = $crate::__rt::min(n, $u.len()); )*
+            let m = n - n % W;
+            axpy![&tail $t W m n; $y ($($u)*); $assign $o ($($parsed)*)]
+        }
+    };
+    [&tail $t:ident $w:ident $m:ident $n:ident; $y:ident (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            for $y in $y[..$m].chunks_exact_mut($w) {
+                axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+            }
+            axpy![&tail $t $w $m $n $y (); $assign $o ($($parsed)*)]
+        }
+    };
+    [&tail $t:ident $w:ident $m:ident $n:ident; $y:ident ($($u:ident)+); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            for ($y, axpy![&pat $($u)+]) in $y[..$m].chunks_exact_mut($w).zip(axpy![&zip exact $w; $($u)+]) {
+                axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+            }
+            axpy![&tail $t $w $m $n $y ($($u)*); $assign $o ($($parsed)*)]
+        }
+    };
+    // (the remainder one element at a time ...)
+    [&tail scalar $w:ident $m:ident $n:ident $y:ident ($($u:ident)*); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            let $y = &mut $y[$m..$n];
+            $( let $u = &$u[$m..$n]; )*
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    // (... as the last w elements, some of them again, which only `=` without reading the target
+    // allows ...)
+    [&tail overlap $w:ident $m:ident $n:ident $y:ident ($($u:ident)*); = $o:tt ($($parsed:tt)*)] => {
+        {
+            axpy![&unread $y $($parsed)*];
+            if $m < $n && $n >= $w {
+                let $y = &mut $y[$n - $w..$n];
+                $( let $u = &$u[$n - $w..$n]; )*
+                axpy![! (emit () $y = $o) ($($parsed)*) + .]
+            } else {
+                axpy![&tail scalar $w $m $n $y ($($u)*); = $o ($($parsed)*)]
+            }
+        }
+    };
+    [&tail overlap $w:ident $m:ident $n:ident $y:ident $u:tt; $assign:tt $o:tt $parsed:tt] =>
+        { compile_error!(concat!("axpy!: tail(overlap, ..) needs `=`, not `", stringify!($assign), "`")) };
+    // (... or as a whole chunk padded with `Default::default()`, of which the first elements are kept)
+    [&tail masked $w:ident $m:ident $n:ident $y:ident ($($u:ident)*); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        if $m < $n {
+            let mut tail = $crate::__rt::pad::<_, $w>(&$y[$m..$n]);
+            {
+                let $y = &mut tail[..];
+                $( let $u = $crate::__rt::pad::<_, $w>(&$u[$m..$n]); )*
+                axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+            }
+            $y[$m..$n].copy_from_slice(&tail[..$n - $m]);
+        }
+    };
+    [&unread $y:ident] => {};
+    [&unread $y:ident , $($rest:tt)*] => { axpy![&unread $y $($rest)*] };
+    [&unread $y:ident $a:tt $op:tt $x:ident $w:tt $($rest:tt)*] => {
+        {
+            macro_rules! unread {
+                ($y) => { compile_error!(concat!("axpy!: tail(overlap, ..) cannot read the target `", stringify!($y), "`")) };
+                ($z:tt) => {};
+            }
+            unread!($x);
+        }
+        axpy![&unread $y $($rest)*]
+    };
+    // (architecture-specific kernels: the coefficients and the slices of the terms are gathered into two arrays, the
+    // signs going into the coefficients, and flipped by `-=`)
+    [&arch $m:tt $y:ident -= ($($a:tt)*) ($($x:ident)*) 0 + $v:ident () $($rest:tt)*] =>
+        { axpy![&arch $m $y -= ($($a)* ($crate::__rt::minus_one())) ($($x)* $v) $($rest)*] };
+    [&arch $m:tt $y:ident -= ($($a:tt)*) ($($x:ident)*) 0 - $v:ident () $($rest:tt)*] =>
+        { axpy![&arch $m $y -= ($($a)* ($crate::__rt::one())) ($($x)* $v) $($rest)*] };
+    [&arch $m:tt $y:ident -= ($($a:tt)*) ($($x:ident)*) (-$c:tt) * $v:ident () $($rest:tt)*] =>
+        { axpy![&arch $m $y -= ($($a)* ($c)) ($($x)* $v) $($rest)*] };
+    [&arch $m:tt $y:ident -= ($($a:tt)*) ($($x:ident)*) $c:tt * $v:ident () $($rest:tt)*] =>
+        { axpy![&arch $m $y -= ($($a)* (-$c)) ($($x)* $v) $($rest)*] };
+    [&arch $m:tt $y:ident $assign:tt ($($a:tt)*) ($($x:ident)*) 0 + $v:ident () $($rest:tt)*] =>
+        { axpy![&arch $m $y $assign ($($a)* ($crate::__rt::one())) ($($x)* $v) $($rest)*] };
+    [&arch $m:tt $y:ident $assign:tt ($($a:tt)*) ($($x:ident)*) 0 - $v:ident () $($rest:tt)*] =>
+        { axpy![&arch $m $y $assign ($($a)* ($crate::__rt::minus_one())) ($($x)* $v) $($rest)*] };
+    [&arch $m:tt $y:ident $assign:tt ($($a:tt)*) ($($x:ident)*) $c:tt * $v:ident () $($rest:tt)*] =>
+        { axpy![&arch $m $y $assign ($($a)* ($c)) ($($x)* $v) $($rest)*] };
+    [&arch $m:tt $y:ident = $a:tt $x:tt] => { axpy![&arch $m $y false $a $x] };
+    [&arch $m:tt $y:ident += $a:tt $x:tt] => { axpy![&arch $m $y true $a $x] };
+    [&arch $m:tt $y:ident -= $a:tt $x:tt] => { axpy![&arch $m $y true $a $x] };
+    [&arch (cuda $h:ident) $y:ident $acc:ident ($($a:tt)+) ($($x:ident)+)] =>
+        { $crate::cuda::combine(&$h, $y.dev_mut(), $acc, [$($a),*], [$($x.dev()),*]) };
+    [&arch $m:tt $y:ident $acc:ident ($($a:tt)+) ($($x:ident)+)] =>
+        { $crate::$m::combine(&mut $y[..], $acc, [$($a),*], [$(&$x[..]),*]) };
+    [&arch $m:tt $y:ident $assign:tt $a:tt $x:tt $($rest:tt)*] =>
+        { compile_error!("axpy!: x86:, aarch64:, riscv64:, wasm32: and cuda(h): support `=`, `+=` and `-=` of sums of scaled slices only") };
+    // (a combination to be evaluated later takes its operands as arguments)
+    [& (lincomb) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        move |$y: &mut [_], $($u: &[_]),*| axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+    };
+    // (ring buffers are evaluated run by run, a run ending wherever any operand's segment does)
+    [& (ring) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            use $crate::ring::Ring;
+            let mut s = 0;
+            loop {
+                let mut e = s + $y.run(s);
+                $( e = $crate::__rt::min(e, s + $u.run(s)); )*
+                if e == s {
+                    break;
+                }
+                {
+                    let $y = $y.segment_mut(s, e);
+                    $( let $u = $u.segment(s, e); )*
+                    axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+                }
+                s = e;
+            }
+        }
+    };
+    // length check with exact: compare the lengths of the target and the slice operands as
+    // strict: does (see prefix=@len), then evaluate the statement on operands cut to the target's
+    // length, which they are now known to have, so that the compiler knows it too and drops the
+    // bounds checks from the loop
+    [& (len exact $k:tt) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        axpy![&len strict;
+            $crate::__rt::same_len(stringify!($y), $y.len(), &[$((stringify!($u), $u.len())),*]);
+            {
+                let n = $y.len();
+                axpy![& (upto n $k) $y ($($u)*) (); $assign $o ($($parsed)*)]
+            }]
+    };
+    [&len strict; $c:expr; $($stmt:tt)*] => {
+        {
+            if let Err(e) = $c {
+                panic!("axpy!: {}", e);
+            }
+            $($stmt)*
+        }
+    };
+    [&len checked; $c:expr; $($stmt:tt)*] => {
+        match $c {
+            Ok(()) => {
+                $($stmt)*;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    };
+    [& (soa) $y:ident ($($u:ident)*) (); $($stmt:tt)*] => {
+        {
+            use $crate::soa::Soa;
+            let mut $y = $y.fields_mut();
+            $( let $u = $u.fields(); )*
+            axpy![& (rows) $y ($($u)*) (); $($stmt)*]
+        }
+    };
+    // const evaluation: index every operand (prefix=&const), the first one also providing the result
+    [&const $i:ident $z:ident; ($($e:tt)*) 0 + $x:ident () $($rest:tt)*] =>
+        { axpy![&const $i $z; ($($e)* + $x[$i]) $($rest)*] };
+    [&const $i:ident $z:ident; ($($e:tt)*) 0 - $x:ident () $($rest:tt)*] =>
+        { axpy![&const $i $z; ($($e)* - $x[$i]) $($rest)*] };
+    [&const $i:ident $z:ident; ($($e:tt)*) (-$a:tt) * $x:ident () $($rest:tt)*] =>
+        { axpy![&const $i $z; ($($e)* - $a * $x[$i]) $($rest)*] };
+    [&const $i:ident $z:ident; ($($e:tt)*) $a:tt * $x:ident () $($rest:tt)*] =>
+        { axpy![&const $i $z; ($($e)* + $a * $x[$i]) $($rest)*] };
+    [&const $i:ident $z:ident; $e:tt $a:tt $op:tt $x:ident $w:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy_const!: `", stringify!($x), "` must be a plain array operand")) };
+    [&const $i:ident $z:ident; ($sign:tt $($e:tt)*)] => {
+        {
+            let mut z = $z;
+            let mut $i = 0;
+            while $i < z.len() {
+                z[$i] = axpy![&const $sign $($e)*];
+                $i += 1;
+            }
+            z
+        }
+    };
+    [&const + $($e:tt)*] => { $($e)* };
+    [&const - $($e:tt)*] => { - $($e)* };
+
+    // checked evaluation: rebind every name to its current element ...
+    [& (checked_ops) $y:ident ($($u:ident)*) (); $assign:tt () ($($parsed:tt)*)] => {
+        {
+            let mut r = Ok(());
+            for (index, ($y, axpy![&pat $($u)*])) in $y.iter_mut().zip(axpy![&zip $($u)*]).enumerate() {
+                match axpy![&ck $y $assign () $($parsed)*] {
+                    Some(v) => *$y = v,
+                    None => {
+                        r = Err($crate::Overflow { index });
+                        break;
+                    }
+                }
+            }
+            r
+        }
+    };
+    [&pat] => { () };
+    [&zip] => { $crate::__rt::repeat(()) };
+    // ... and chain the checked operations, s being the partial sum (the coefficient is always
+    // the argument, so that integer literals need no type annotation)
+    [&ck $y:ident $assign:tt () 0 + $x:ident ($($w:tt)*) $($rest:tt)*] =>
+        { axpy![&ck $y $assign (Some(((*$x)$($w)*))) $($rest)*] };
+    [&ck $y:ident $assign:tt () 0 - $x:ident ($($w:tt)*) $($rest:tt)*] =>
+        { axpy![&ck $y $assign (((*$x)$($w)*).checked_neg()) $($rest)*] };
+    [&ck $y:ident $assign:tt () (-$a:tt) * $x:ident ($($w:tt)*) $($rest:tt)*] =>
+        { axpy![&ck $y $assign (((*$x)$($w)*).checked_mul($a).and_then(|t| t.checked_neg())) $($rest)*] };
+    [&ck $y:ident $assign:tt () $a:tt * $x:ident ($($w:tt)*) $($rest:tt)*] =>
+        { axpy![&ck $y $assign (((*$x)$($w)*).checked_mul($a)) $($rest)*] };
+    [&ck $y:ident $assign:tt ($($s:tt)+) 0 + $x:ident ($($w:tt)*) $($rest:tt)*] =>
+        { axpy![&ck $y $assign ($($s)*.and_then(|s| s.checked_add(((*$x)$($w)*)))) $($rest)*] };
+    [&ck $y:ident $assign:tt ($($s:tt)+) 0 - $x:ident ($($w:tt)*) $($rest:tt)*] =>
+        { axpy![&ck $y $assign ($($s)*.and_then(|s| s.checked_sub(((*$x)$($w)*)))) $($rest)*] };
+    [&ck $y:ident $assign:tt ($($s:tt)+) (-$a:tt) * $x:ident ($($w:tt)*) $($rest:tt)*] => {
+        axpy![&ck $y $assign
+            ($($s)*.and_then(|s| ((*$x)$($w)*).checked_mul($a).and_then(|t| s.checked_sub(t)))) $($rest)*]
+    };
+    [&ck $y:ident $assign:tt ($($s:tt)+) $a:tt * $x:ident ($($w:tt)*) $($rest:tt)*] => {
+        axpy![&ck $y $assign
+            ($($s)*.and_then(|s| ((*$x)$($w)*).checked_mul($a).and_then(|t| s.checked_add(t)))) $($rest)*]
+    };
+    [&ck $y:ident = ($($s:tt)+)] => { $($s)* };
+    [&ck $y:ident += ($($s:tt)+)] => { $($s)*.and_then(|s| (*$y).checked_add(s)) };
+    [&ck $y:ident -= ($($s:tt)+)] => { $($s)*.and_then(|s| (*$y).checked_sub(s)) };
+    [&ck $y:ident $assign:tt ($($s:tt)+)] =>
+        { compile_error!(concat!("axpy!: checked_ops: supports `=`, `+=` and `-=`, not `", stringify!($assign), "`")) };
+
+    // NaN/Inf hunting: in debug builds, rebind every name to a slice of its current element alone,
+    // evaluate the unchanged statement on that, and look at the value it produced
+    [& (checked_fp) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        if cfg!(debug_assertions) {
+            for (index, ($y, axpy![&pat $($u)*])) in $y.iter_mut().zip(axpy![&zip $($u)*]).enumerate() {
+                let was = *$y;
+                {
+                    let $y = $crate::__rt::from_mut(&mut *$y);
+                    $( let $u = $crate::__rt::from_ref($u); )*
+                    axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+                }
+                if !$y.is_finite() {
+                    panic!(concat!("axpy!: checked_fp: ", stringify!($y), "[{}] became {:?}, from ",
+                                   stringify!($y), " = {:?}", $(", ", stringify!($u), " = {:?}",)*),
+                           index, *$y, was $(, $u)*);
+                }
+            }
+        } else {
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+    // blocked reductions: rebind every name to its current block, and fold each into its own result
+    [& (blocks $b:ident $out:ident $f:ident) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            assert!($b > 0, "axpy!: the block size must be positive");
+            let mut n = usize::MAX;
+            $( n = $crate::__rt::min(n, $u.len()); )*
+            for (k, out) in $out.iter_mut().enumerate() {
+                let s = k.saturating_mul($b);
+                if s >= n {
+                    break;
+                }
+                let e = $crate::__rt::min(n, s.saturating_add($b));
+                $( let $u = $crate::__rt::tile(&$u[..], s, e); )*
+                *out = axpy![! (emit (fold $f) $y $assign $o) ($($parsed)*) + .];
+            }
+        }
+    };
+
+    // volatile accesses: rebind every name to a copy of its current element, loaded once, and
+    // store the result back
+    [& (volatile) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            $crate::__rt::fence();
+            for (store, axpy![&pat $($u)*]) in $y.iter_mut().zip(axpy![&zip $($u)*]) {
+                let mut $y = [$crate::__rt::load(&*store)];
+                $( let $u = [$crate::__rt::load($u)]; )*
+                axpy![! (emit () $y $assign $o) ($($parsed)*) + .];
+                $crate::__rt::store(store, $y[0]);
+            }
+            $crate::__rt::fence();
+        }
+    };
+
+    // stochastic rounding: rebind every name to its current element, widened, evaluate the
+    // unchanged statement on that, and round the result as it is stored
+    [& (stochastic $r:ident) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            use $crate::stochastic::Stochastic;
+            for (store, axpy![&pat $($u)*]) in $y.iter_mut().zip(axpy![&zip $($u)*]) {
+                let mut $y = [store.widen()];
+                $( let $u = [$u.widen()]; )*
+                axpy![! (emit () $y $assign $o) ($($parsed)*) + .];
+                *store = Stochastic::round($y[0], $r.next_u32());
+            }
+        }
+    };
+
+    // n-dimensional arrays: flattened to their memory-order slices if they all have one, with
+    // the same shape and strides, or else iterated element by element in logical order
+    [& (nd) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        if $y.as_slice_memory_order().is_some()
+            $( && $u.shape() == $y.shape() && $u.strides() == $y.strides() && $u.as_slice_memory_order().is_some() )*
+        {
+            let $y = $y.as_slice_memory_order_mut().unwrap();
+            $( let $u = $u.as_slice_memory_order().unwrap(); )*
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        } else {
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+
+    // (a block: the targets are cut to the chunk, and so are the sources, which are the other names)
+    [& (block) $y:ident ($($u:ident)*) (); ($w:expr; $h:tt) ($($t:ident)*) ($($z:ident)*) ($([$d:ident $assign:tt $o:tt $parsed:tt])*)] => {
+        {
+            const W: usize = $w;
+            assert!(W > 0, "axpy!: the block width must be positive");
+            macro_rules! source {
+                $( ($t len $n:ident) => {}; ($t cut $s:ident $e:ident) => {}; )*
+                $( ($z len $n:ident) => {}; ($z cut $s:ident $e:ident) => {}; )*
+                ($x:ident len $n:ident) => { $n = $crate::__rt::min($n, $x.len()); };
+                ($x:ident cut $s:ident $e:ident) => { let $x = $crate::__rt::tile(&$x[..], $s, $e); };
+            }
+            let mut n = usize::MAX;
+            $( n = $crate::__rt::min(n, $z.len()); )*
+            $( source!($u len n); )*
+            $( let mut $t = [Default::default(); W]; )*
+            let mut s = 0;
+            while s < n {
+                let e = $crate::__rt::min(n, s + W);
+                axpy![;hook $h s e {
+                    $( let $z = $crate::__rt::tile_mut(&mut $z[..], s, e); )*
+                    $( source!($u cut s e); )*
+                    $( let $t = &mut $t[..e - s]; )*
+                    $( axpy![! (emit () $d $assign $o) $parsed + .]; )*
+                }];
+                s = e;
+            }
+        }
+    };
+
+    // small vectors: every operand is rebound to the slice of its components
+    [& (components $s:ident) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            let $y = $crate::__rt::components_mut::<_, $s>(&mut $y[..]);
+            $( let $u = $crate::__rt::components::<_, $s>(&$u[..]); )*
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        }
+    };
 
+    // faer columns: as slices if they all have unit stride, or else element by element, every name
+    // rebound to its element as for checked_fp:
+    [& (faer) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        if $y.rb().try_as_slice().is_some() $( && $u.rb().try_as_slice().is_some() )* {
+            let $y = $y.rb_mut().try_as_slice_mut().unwrap();
+            $( let $u = $u.rb().try_as_slice().unwrap(); )*
+            axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+        } else {
+            let mut n = $y.nrows();
+            $( n = $crate::__rt::min(n, $u.nrows()); )*
+            for i in 0..n {
+                let mut elem = [$y[i]];
+                {
+                    let $y = &mut elem;
+                    $( let $u = [$u[i]]; )*
+                    axpy![! (emit () $y $assign $o) ($($parsed)*) + .]
+                }
+                $y[i] = elem[0];
+            }
+        }
+    };
+
+    // single index: every slice operand cut to its element i, and the one value taken
+    [& (at $i:ident) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            $( let $u = $crate::__rt::from_ref(&$u[$i]); )*
+            axpy![! (emit (fold first) $y $assign $o) ($($parsed)*) + .]
+        }
+    };
+
+    // atomic targets: the right-hand side is evaluated into a local of one element, with every
+    // name (the target's loaded) rebound to its element, and applied by a single atomic operation
+    [& (atomic) $y:ident () (); $assign:tt $o:tt $parsed:tt] => {
+        for store in $y.iter() {
+            axpy![;atomic store $y; $assign $o $parsed];
+        }
+    };
+    [& (atomic) $y:ident ($($u:ident)+) (); $assign:tt $o:tt $parsed:tt] => {
+        for (store, axpy![&pat $($u)+]) in $y.iter().zip(axpy![&zip $($u)+]) {
+            $( let $u = [*$u]; )*
+            axpy![;atomic store $y; $assign $o $parsed];
+        }
+    };
+    [;atomic $s:ident $y:ident; $assign:tt $o:tt ($($parsed:tt)*)] => {
+        #[allow(unused_variables)]
+        let $y = [$crate::atomic::Atomic::load($s, $crate::atomic::Relaxed)];
+        let mut __axpy_atomic = $y;
+        axpy![! (emit () __axpy_atomic = $o) ($($parsed)*) + .];
+        axpy![;atomic $assign $s __axpy_atomic[0]];
+    };
+    [;atomic += $s:ident $v:expr] => { $crate::atomic::Atomic::fetch_add($s, $v, $crate::atomic::Relaxed); };
+    [;atomic -= $s:ident $v:expr] => { $crate::atomic::Atomic::fetch_sub($s, $v, $crate::atomic::Relaxed); };
+    [;atomic = $s:ident $v:expr] => { $crate::atomic::Atomic::store($s, $v, $crate::atomic::Relaxed); };
+    [;atomic $assign:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy!: atomic: expected `+=`, `-=` or `=`, found `", stringify!($assign), "`")) };
+
+    // mapped operands: the statement on every chunk of the target as on a tile, after reading in
+    // the chunk's pages of each slice operand in turn
+    [& (mmap) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            let mut n = $y.len();
+            $( n = $crate::__rt::min(n, $u.len()); )*
+            let b = $crate::mmap::chunk_len(&$y[..]);
+            let mut s = 0;
+            while s < n {
+                let e = $crate::__rt::min(n, s.saturating_add(b));
+                $( $crate::mmap::touch($crate::__rt::tile(&$u[..], s, e)); )*
+                $crate::mmap::touch(&$y[s..e]);
+                axpy![& (tile s e) $y ($($u)*) (); $assign $o ($($parsed)*)];
+                s = e;
+            }
+        }
+    };
+
+    // arbitrary precision: every element is accumulated in acc (prefix=;mp), from scratch for `=`
+    // or from the target's element for `+=` and `-=`, scaled terms going through t, after which
+    // acc is swapped into the target, the old element becoming the next accumulator
+    // (the sign the terms are added with is bracketed, so that any other assignment reaches the
+    // last arm)
+    [& (rug) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            let mut n = $y.len();
+            $( n = $crate::__rt::min(n, $u.len()); )*
+            if n > 0 {
+                let mut acc = $y[0].clone();
+                let mut t = acc.clone();
+                for i in 0..n {
+                    axpy![;mp $assign (acc t i $y) $($parsed)*];
+                    $crate::__rt::swap(&mut $y[i], &mut acc);
+                }
+            }
+        }
+    };
+    [;mp = ($acc:ident $t:ident $i:ident $y:ident) $a:tt $op:tt $x:ident $w:tt $($rest:tt)*] => {
+        $acc.clone_from(&$x[$i]);
+        axpy![;scale $acc $a $op $w];
+        axpy![;neg $op $acc];
+        axpy![;mp [+] ($acc $t $i $y) $($rest)*];
+    };
+    [;mp += ($acc:ident $t:ident $i:ident $y:ident) $($rest:tt)*] => {
+        $acc.clone_from(&$y[$i]);
+        axpy![;mp [+] ($acc $t $i $y) $($rest)*];
+    };
+    [;mp -= ($acc:ident $t:ident $i:ident $y:ident) $($rest:tt)*] => {
+        $acc.clone_from(&$y[$i]);
+        axpy![;mp [-] ($acc $t $i $y) $($rest)*];
+    };
+    [;mp [$s:tt] $v:tt] => {};
+    [;mp [$s:tt] ($acc:ident $t:ident $i:ident $y:ident) 0 $op:tt $x:ident () $($rest:tt)*] => {
+        axpy![;add $s $op $acc (&$x[$i])];
+        axpy![;mp [$s] ($acc $t $i $y) $($rest)*];
+    };
+    [;mp [$s:tt] ($acc:ident $t:ident $i:ident $y:ident) $a:tt $op:tt $x:ident $w:tt $($rest:tt)*] => {
+        $t.clone_from(&$x[$i]);
+        axpy![;scale $t $a $op $w];
+        axpy![;add $s $op $acc (&$t)];
+        axpy![;mp [$s] ($acc $t $i $y) $($rest)*];
+    };
+    [;mp $assign:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy!: rug: expected `=`, `+=` or `-=`, found `", stringify!($assign), "`")) };
+    // (a parenthesized coefficient loses its parentheses, `*=` binding the least tightly anyway)
+    [;scale $t:ident ($($a:tt)+) * $w:tt] => { $t *= $($a)*; axpy![;scale $t 0 + $w]; };
+    [;scale $t:ident $a:tt * $w:tt] => { $t *= $a; axpy![;scale $t 0 + $w]; };
+    [;scale $t:ident 0 $op:tt ()] => {};
+    [;scale $t:ident 0 $op:tt (* ($($b:tt)+))] => { $t *= $($b)*; };
+    [;scale $t:ident 0 $op:tt (* $b:tt)] => { $t *= $b; };
+    [;scale $t:ident 0 $op:tt (div ($($d:tt)+))] => { $t /= $($d)*; };
+    [;scale $t:ident 0 $op:tt (div $d:tt)] => { $t /= $d; };
+    [;scale $t:ident 0 $op:tt $w:tt] =>
+        { compile_error!("axpy!: rug: supports the terms `± x`, `± a*x` and `x/d` only") };
+    [;neg - $acc:ident] => { $acc = -$acc; };
+    [;neg $op:tt $acc:ident] => {};
+    [;add + - $acc:ident $e:tt] => { $acc -= $e; };
+    [;add - + $acc:ident $e:tt] => { $acc -= $e; };
+    [;add - * $acc:ident $e:tt] => { $acc -= $e; };
+    [;add - - $acc:ident $e:tt] => { $acc += $e; };
+    [;add + $op:tt $acc:ident $e:tt] => { $acc += $e; };
+
+    // quantized evaluation: the same with every name rebound to its dequantized element, the
+    // operands dequantizing as they are iterated
+    [& (quant) $y:ident ($($u:ident)*) (); $assign:tt $o:tt ($($parsed:tt)*)] => {
+        {
+            let q = $crate::quant::Quant::new((), $y.scale, $y.zero);
+            for (store, axpy![&pat $($u)*]) in (&mut $y.data[..]).iter_mut().zip(axpy![&zip $($u)*]) {
+                let mut $y = [q.dequantize(*store)];
+                $( let $u = [$u]; )*
+                axpy![! (emit () $y $assign $o) ($($parsed)*) + .];
+                *store = q.quantize($y[0]);
+            }
+        }
+    };
+
+    // complex evaluation: rebind every name to its current element, viewed as [re, im] ...
+    [& (complex) $y:ident () (); $assign:tt () ($($parsed:tt)*)] => {
+        for $y in $crate::__rt::pairs_mut(&mut $y[..]).iter_mut() {
+            axpy![&cx $y $assign () () $($parsed)*]
+        }
+    };
+    [& (complex) $y:ident ($($u:ident)+) (); $assign:tt () ($($parsed:tt)*)] => {
+        {
+            $( let $u = $crate::__rt::pairs(&$u[..]); )*
+            for ($y, axpy![&pat $($u)+]) in $crate::__rt::pairs_mut(&mut $y[..]).iter_mut().zip(axpy![&zip $($u)+]) {
+                axpy![&cx $y $assign () () $($parsed)*]
+            }
+        }
+    };
+    // ... and spell out both parts of every term, where c = a.parts() for a coefficient a:
+    //     c * x  =>  re: c[0] * x[0] - c[1] * x[1],  im: c[0] * x[1] + c[1] * x[0]
+    [&cx $y:ident $assign:tt (+ $($re:tt)*) (+ $($im:tt)*)] => {
+        {
+            let re = $($re)*;
+            let im = $($im)*;
+            $y[0] $assign re;
+            $y[1] $assign im;
+        }
+    };
+    [&cx $y:ident $assign:tt ($($re:tt)*) ($($im:tt)*) 0 + $x:ident () $($rest:tt)*] =>
+        { axpy![&cx $y $assign ($($re)* + $x[0]) ($($im)* + $x[1]) $($rest)*] };
+    [&cx $y:ident $assign:tt ($($re:tt)*) ($($im:tt)*) 0 - $x:ident () $($rest:tt)*] =>
+        { axpy![&cx $y $assign ($($re)* - $x[0]) ($($im)* - $x[1]) $($rest)*] };
+    [&cx $y:ident $assign:tt ($($re:tt)*) ($($im:tt)*) (-$a:tt) * $x:ident () $($rest:tt)*] => {
+        axpy![&cx $y $assign
+            ($($re)* - ($crate::__rt::parts($a)[0] * $x[0] - $crate::__rt::parts($a)[1] * $x[1]))
+            ($($im)* - ($crate::__rt::parts($a)[0] * $x[1] + $crate::__rt::parts($a)[1] * $x[0]))
+            $($rest)*]
+    };
+    [&cx $y:ident $assign:tt ($($re:tt)*) ($($im:tt)*) $a:tt * $x:ident () $($rest:tt)*] => {
+        axpy![&cx $y $assign
+            ($($re)* + ($crate::__rt::parts($a)[0] * $x[0] - $crate::__rt::parts($a)[1] * $x[1]))
+            ($($im)* + ($crate::__rt::parts($a)[0] * $x[1] + $crate::__rt::parts($a)[1] * $x[0]))
+            $($rest)*]
+    };
+    [&cx $y:ident $assign:tt $re:tt $im:tt $a:tt $op:tt $x:ident $w:tt $($rest:tt)*] =>
+        { compile_error!(concat!("axpy!: complex operands cannot be functions, as in `", stringify!($w), "`")) };
+
+    // iterator, macro and column operands are not gathered, in the modes that accept them (prefix=&elem)
+    [& $m:tt $y:ident $u:tt ($a:tt $op:tt $x:ident (iter $($w:tt)*) $($rest:tt)*); $($stmt:tt)*] =>
+        { axpy![&elem $m $x [& $m $y $u ($($rest)*); $($stmt)*]] };
+    [& $m:tt $y:ident $u:tt ($a:tt $op:tt $x:ident (mac $($w:tt)*) $($rest:tt)*); $($stmt:tt)*] =>
+        { axpy![&elem $m $x [& $m $y $u ($($rest)*); $($stmt)*]] };
+    [& $m:tt $y:ident $u:tt ($a:tt $op:tt $x:ident (col $($w:tt)*) $($rest:tt)*); $($stmt:tt)*] =>
+        { axpy![&elem $m $x [& $m $y $u ($($rest)*); $($stmt)*]] };
+    // (a reversed operand depends on the rows of the loop, which these modes change)
+    [& $m:tt $y:ident $u:tt ($a:tt $op:tt $x:ident (rev) $($rest:tt)*); $($stmt:tt)*] =>
+        { compile_error!(concat!("axpy!: rev(", stringify!($x), ") is supported in plain statements only")) };
+    // the modes that rebind every name to its element, or cut it to a block, take slices only
+    [&elem (checked_fp) $x:ident $next:tt] => { axpy![&elem checked_fp $x] };
+    [&elem (blocks $($p:tt)*) $x:ident $next:tt] => { axpy![&elem blocks $x] };
+    [&elem (volatile) $x:ident $next:tt] => { axpy![&elem volatile $x] };
+    [&elem (stochastic $($p:tt)*) $x:ident $next:tt] => { axpy![&elem stochastic $x] };
+    [&elem (block) $x:ident $next:tt] => { axpy![&elem block $x] };
+    [&elem (at $($p:tt)*) $x:ident $next:tt] => { axpy![&elem axpy_at $x] };
+    [&elem (atomic) $x:ident $next:tt] => { axpy![&elem atomic $x] };
+    [&elem (rug) $x:ident $next:tt] => { axpy![&elem rug $x] };
+    [&elem (quant) $x:ident $next:tt] => { axpy![&elem quant $x] };
+    [&elem $m:tt $x:ident [$($next:tt)*]] => { axpy![$($next)*] };
+    [&elem $p:ident $x:ident] => {
+        compile_error!(concat!("axpy!: ", stringify!($p), ": supports slice operands only, not `", stringify!($x), "`"))
+    };
+    [& $m:tt $y:ident ($($u:ident)*) (, $($rest:tt)*); $($stmt:tt)*] => {
+        axpy![& $m $y ($($u)*) ($($rest)*); $($stmt)*]
+    };
+    [& $m:tt $y:ident ($($u:ident)*) ($a:tt $op:tt $x:ident $w:tt $($rest:tt)*); $($stmt:tt)*] => {
+        {
+            macro_rules! eval {
+                ($p:tt $q:tt $y) => { axpy!$p };
+                $( ($p:tt $q:tt $u) => { axpy!$p }; )*
+                ($p:tt $q:tt $x) => { axpy!$q };
+            }
+            eval!([& $m $y ($($u)*) ($($rest)*); $($stmt)*] [& $m $y ($($u)* $x) ($($rest)*); $($stmt)*] $x)
+        }
+    };
 }
 
+mod level1;
+mod poly;
+#[macro_use]
+mod reduce;
+mod level2;
+mod conv;
+mod expand;
+mod at;
+mod konst;
+mod scan;
+mod lincomb;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "std")]
+mod tune;
+#[cfg(feature = "std")]
+mod norm;
+
+mod len;
+pub use len::LengthMismatch;
+mod mp;
+mod operand;
+pub use operand::{AxpySource, AxpyTarget};
+mod checked;
+pub use checked::Overflow;
+mod components;
+pub use components::Components;
+#[cfg(doctest)]
+mod compile_fail;
+
+pub mod acc;
+#[cfg(all(feature = "arch-aarch64", target_arch = "aarch64"))]
+pub mod aarch64;
+pub mod atomic;
+pub mod blas;
+pub mod complex;
+#[cfg(feature = "cuda")]
+pub mod cuda;
+pub mod exec;
+pub mod expr;
+pub mod guide;
+#[cfg(feature = "memmap")]
+pub mod mmap;
+pub mod quant;
+pub mod ring;
+#[cfg(all(feature = "arch-riscv64", target_arch = "riscv64", target_feature = "v"))]
+pub mod riscv64;
+pub mod smooth;
+pub mod soa;
+pub mod stochastic;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub mod wasm32;
+#[cfg(all(feature = "arch-x86", target_arch = "x86_64"))]
+pub mod x86;
+
+#[doc(hidden)]
+pub mod __rt {
+    pub use core::slice::{from_mut, from_raw_parts, from_raw_parts_mut, from_ref};
+    use core::ops::{Add, Deref, Div, Mul, Neg, Sub};
+    use core::num::{Saturating, Wrapping};
+    use acc;
+    use complex::{Coef, Complex};
+    use Components;
+    use LengthMismatch;
+    pub use core::iter::repeat;
+    pub use core::mem::swap;
+    #[cfg(feature = "std")]
+    pub use tune::{Probe, Tuner};
+    #[cfg(feature = "std")]
+    pub use norm::{nrm2, nrm2_kahan, nrm2_neumaier};
+    #[cfg(feature = "std")]
+    pub use std::time::Instant;
+    #[cfg(feature = "alloc")]
+    pub use alloc::vec::Vec;
+
+    /// Larger of `a` and `b`, written as a select so that it maps onto vector max instructions
+    /// (when either is NaN, `a` is returned).
+    #[inline(always)]
+    pub fn max<T: PartialOrd>(a: T, b: T) -> T {
+        if a < b { b } else { a }
+    }
+
+    /// Smaller of `a` and `b` (when either is NaN, `a` is returned).
+    #[inline(always)]
+    pub fn min<T: PartialOrd>(a: T, b: T) -> T {
+        if b < a { b } else { a }
+    }
+
+    /// `a * b`, the outer function of `dot!` of combinations.
+    #[inline(always)]
+    pub fn mul<T: Mul<Output = T>>(a: T, b: T) -> T {
+        a * b
+    }
+
+    /// Magnitude of `x`, as a select.
+    #[inline(always)]
+    pub fn abs<T: Copy + PartialOrd + Neg<Output = T>>(x: T) -> T {
+        max(x, -x)
+    }
+
+    /// `x` restricted to `[lo, hi]` (a NaN `x` passes through).
+    #[inline(always)]
+    pub fn clamp<T: PartialOrd>(x: T, lo: T, hi: T) -> T {
+        if x < lo { lo } else if hi < x { hi } else { x }
+    }
+
+    /// The term `a * x`. Scalars of the element type scale it directly, but for the newtypes
+    /// `Wrapping<T>` and `Saturating<T>` a coefficient of the inner type `T` is wrapped first (there
+    /// being no `T: Mul<Wrapping<T>>`). The choice is made by method resolution: `Scale::get`
+    /// exists for the newtypes only, and otherwise `Scale` dereferences to `Term`, whose `get` is
+    /// the plain product.
+    pub struct Scale<C, X>(pub Term<C, X>);
+    pub struct Term<C, X>(pub C, pub X);
+
+    impl<C, X> Deref for Scale<C, X> {
+        type Target = Term<C, X>;
+        #[inline(always)]
+        fn deref(&self) -> &Term<C, X> {
+            &self.0
+        }
+    }
+
+    impl<C: Copy + Mul<X>, X: Copy> Term<C, X> {
+        #[inline(always)]
+        pub fn get(&self) -> C::Output {
+            self.0 * self.1
+        }
+    }
+
+    impl<T: Copy> Scale<T, Wrapping<T>> where Wrapping<T>: Mul {
+        #[inline(always)]
+        pub fn get(&self) -> <Wrapping<T> as Mul>::Output {
+            Wrapping((self.0).0) * (self.0).1
+        }
+    }
+
+    impl<T: Copy> Scale<T, Saturating<T>> where Saturating<T>: Mul {
+        #[inline(always)]
+        pub fn get(&self) -> <Saturating<T> as Mul>::Output {
+            Saturating((self.0).0) * (self.0).1
+        }
+    }
+
+    /// The quotient `x / d`. Floating-point elements are multiplied by the reciprocal instead,
+    /// which the optimizer hoists out of the loop, whereas any other type is divided, through the
+    /// `Deref` to `Ratio` (the same dispatch as for `Scale`).
+    pub struct Quot<X, D>(pub Ratio<X, D>);
+    pub struct Ratio<X, D>(pub X, pub D);
+
+    impl<X, D> Deref for Quot<X, D> {
+        type Target = Ratio<X, D>;
+        #[inline(always)]
+        fn deref(&self) -> &Ratio<X, D> {
+            &self.0
+        }
+    }
+
+    impl<X: Copy + Div<D>, D: Copy> Ratio<X, D> {
+        #[inline(always)]
+        pub fn get(&self) -> X::Output {
+            self.0 / self.1
+        }
+    }
+
+    impl Quot<f32, f32> {
+        #[inline(always)]
+        pub fn get(&self) -> f32 {
+            (self.0).0 * (1. / (self.0).1)
+        }
+    }
+
+    impl Quot<f64, f64> {
+        #[inline(always)]
+        pub fn get(&self) -> f64 {
+            (self.0).0 * (1. / (self.0).1)
+        }
+    }
+
+    /// The address range `[start, end)` of the `n` elements at `p`.
+    #[inline(always)]
+    pub fn span<T>(p: *const T, n: usize) -> (usize, usize) {
+        (p as usize, (p as usize).wrapping_add(n.wrapping_mul(core::mem::size_of::<T>())))
+    }
+
+    /// Panics if the range of an operand overlaps that of the target, as reading the one while
+    /// writing the other would be undefined behaviour (used by `ptr(n):` in debug builds).
+    #[inline(never)]
+    pub fn no_alias(target: &'static str, y: (usize, usize), operands: &[(&'static str, (usize, usize))]) {
+        for &(operand, x) in operands {
+            if x.0 < y.1 && y.0 < x.1 {
+                panic!("axpy!: the operand `{}` ({:#x}..{:#x}) overlaps the target `{}` ({:#x}..{:#x})",
+                       operand, x.0, x.1, target, y.0, y.1);
+            }
+        }
+    }
+
+    /// The components of the small vectors `x`, of type `S` (see `components(S):`).
+    #[inline(always)]
+    pub fn components<V: Components<S>, S>(x: &[V]) -> &[S] {
+        unsafe { from_raw_parts(x.as_ptr() as *const S, x.len() * component_count::<V, S>()) }
+    }
+
+    /// The components of the small vectors `x`, mutably.
+    #[inline(always)]
+    pub fn components_mut<V: Components<S>, S>(x: &mut [V]) -> &mut [S] {
+        unsafe { from_raw_parts_mut(x.as_mut_ptr() as *mut S, x.len() * component_count::<V, S>()) }
+    }
+
+    fn component_count<V, S>() -> usize {
+        let (v, s) = (core::mem::size_of::<V>(), core::mem::size_of::<S>());
+        assert!(s > 0 && v % s == 0 && core::mem::align_of::<V>() >= core::mem::align_of::<S>(),
+                "axpy!: components: the vectors are not made of whole components");
+        v / s
+    }
+
+    /// A sparse operand's index as a position (of any integer type that fits).
+    #[inline(always)]
+    pub fn index<I>(i: I) -> usize where usize: core::convert::TryFrom<I> {
+        <usize as core::convert::TryFrom<I>>::try_from(i).ok().expect("axpy!: sparse index out of range of usize")
+    }
+
+    /// Checks that every operand has as many elements as the target.
+    #[inline]
+    pub fn same_len(target: &'static str, n: usize, operands: &[(&'static str, usize)])
+        -> Result<(), LengthMismatch>
+    {
+        for &(operand, len) in operands {
+            if len != n {
+                return Err(LengthMismatch { operand, len, target, expected: n });
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs `s = a*s + z[i]; z[i] = s` over `z`, returning the last `s`.
+    #[inline]
+    pub fn recur<T: Copy + Add<Output = T> + Mul<Output = T>>(z: &mut [T], a: T, mut s: T) -> T {
+        let mut chunks = z.chunks_exact_mut(4);
+        for c in &mut chunks {
+            s = a * s + c[0];
+            c[0] = s;
+            s = a * s + c[1];
+            c[1] = s;
+            s = a * s + c[2];
+            c[2] = s;
+            s = a * s + c[3];
+            c[3] = s;
+        }
+        for v in chunks.into_remainder() {
+            s = a * s + *v;
+            *v = s;
+        }
+        s
+    }
+
+    /// Runs `s = s + x[i]; y[i] = s` over `y` (with `x` being `y` itself if `None`, and `s` starting
+    /// at the first element if `None`), eight elements at a time, returning the last `s`.
+    #[inline]
+    pub fn cumsum<T: Copy + Add<Output = T>>(y: &mut [T], x: Option<&[T]>, mut s: Option<T>) -> Option<T> {
+        use core::convert::TryFrom;
+        let n = x.map_or(y.len(), |x| min(y.len(), x.len()));
+        let whole = n - n % 8;
+        for k in (0..whole).step_by(8) {
+            let block = match x { Some(x) => &x[k..k + 8], None => &y[k..k + 8] };
+            let mut b = <[T; 8]>::try_from(block).expect("blocks have eight elements");
+            for d in [1, 2, 4] {
+                for i in (d..8).rev() {
+                    b[i] = b[i - d] + b[i];
+                }
+            }
+            if let Some(s) = s {
+                for v in &mut b {
+                    *v = s + *v;
+                }
+            }
+            s = Some(b[7]);
+            y[k..k + 8].copy_from_slice(&b);
+        }
+        for (i, y) in y[whole..n].iter_mut().enumerate() {
+            let v = x.map_or(*y, |x| x[whole + i]);
+            let v = s.map_or(v, |s| s + v);
+            *y = v;
+            s = Some(v);
+        }
+        s
+    }
+
+    /// Slices of complex numbers as slices of `[re, im]`.
+    #[inline(always)]
+    pub fn pairs<C: Complex>(x: &[C]) -> &[[C::Real; 2]] {
+        unsafe { from_raw_parts(x.as_ptr() as *const [C::Real; 2], x.len()) }
+    }
+
+    #[inline(always)]
+    pub fn pairs_mut<C: Complex>(x: &mut [C]) -> &mut [[C::Real; 2]] {
+        unsafe { from_raw_parts_mut(x.as_mut_ptr() as *mut [C::Real; 2], x.len()) }
+    }
+
+    /// The parts of `sum(conj(x[i]) * y[i])`.
+    #[inline(always)]
+    pub fn dotc<R>(x: &[[R; 2]], y: &[[R; 2]]) -> [R; 2]
+        where R: Copy + Default + Add<Output = R> + Sub<Output = R> + Mul<Output = R>
+    {
+        let (mut re, mut im) = (R::default(), R::default());
+        for (x, y) in x.iter().zip(y.iter()) {
+            re = re + (x[0] * y[0] + x[1] * y[1]);
+            im = im + (x[0] * y[1] - x[1] * y[0]);
+        }
+        [re, im]
+    }
+
+    /// The real and imaginary parts of a complex or real coefficient.
+    #[inline(always)]
+    pub fn parts<R, C: Coef<R>>(a: C) -> [R; 2] {
+        a.parts()
+    }
+
+    /// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]`, or `y += ...` if `accumulate`, one element at a
+    /// time, for the remainders of the architecture-specific kernels.
+    #[inline(always)]
+    pub fn combine<T, const N: usize>(y: &mut [T], accumulate: bool, a: [T; N], x: [&[T]; N])
+        where T: Copy + Default + Add<Output = T> + Mul<Output = T>
+    {
+        for (i, y) in y.iter_mut().enumerate() {
+            let mut s = if accumulate { *y } else { T::default() };
+            for k in 0..N {
+                s = s + a[k] * x[k][i];
+            }
+            *y = s;
+        }
+    }
+
+    /// The coefficient of a term without one.
+    #[inline(always)]
+    pub fn one<T: From<u8>>() -> T {
+        T::from(1)
+    }
+
+    /// The coefficient of a negated term without one.
+    #[inline(always)]
+    pub fn minus_one<T: From<i8>>() -> T {
+        T::from(-1)
+    }
+
+    /// `x` followed by default values, up to `W` elements.
+    #[inline(always)]
+    pub fn pad<T: Copy + Default, const W: usize>(x: &[T]) -> [T; W] {
+        let mut b = [T::default(); W];
+        b[..x.len()].copy_from_slice(x);
+        b
+    }
+
+    /// `i + k` clamped to the indices of `n` elements.
+    #[inline(always)]
+    pub fn clamped(i: usize, k: isize, n: usize) -> usize {
+        min(i.saturating_add_signed(k), n - 1)
+    }
+
+    /// `i + k` wrapped around to the indices of `n` elements.
+    #[inline(always)]
+    pub fn wrapped(i: usize, k: isize, n: usize) -> usize {
+        (i as isize + k).rem_euclid(n as isize) as usize
+    }
+
+    /// A rotation by `k` of `n` elements as one in `0..n` (0 for no elements).
+    #[inline(always)]
+    pub fn rotation(k: isize, n: usize) -> usize {
+        if n == 0 { 0 } else { k.rem_euclid(n as isize) as usize }
+    }
+
+    /// The elements `s..e` of `x` rotated by `k`, which wrap around before `s` or after `e` only.
+    #[inline(always)]
+    pub fn rotated<T>(x: &[T], k: isize, s: usize, e: usize) -> &[T] {
+        let n = x.len();
+        let k = rotation(k, n);
+        if s + k < n { &x[s + k..e + k] } else { &x[s + k - n..e + k - n] }
+    }
+
+    /// The sum over the taps of a convolution, `k[0]*w[N - 1] + ... + k[N - 1]*w[0]`, for a window
+    /// `w` of `N` elements.
+    #[inline(always)]
+    pub fn fir<K: Copy + Mul<T, Output = T>, T: Copy + Add<Output = T>, const N: usize>(k: &[K; N], w: &[T]) -> T {
+        let w = <&[T; N] as core::convert::TryFrom<&[T]>>::try_from(w).expect("axpy!: conv: window of the wrong length");
+        let mut s = k[0] * w[N - 1];
+        for (k, w) in k[1..].iter().zip(w[..N - 1].iter().rev()) {
+            s = s + *k * *w;
+        }
+        s
+    }
+
+    /// `a[i]*x[i + k]`, or the default (zero) if `i + k` is outside `x`.
+    #[inline(always)]
+    pub fn band<T: Copy + Default + Mul<Output = T>>(a: &[T], x: &[T], i: usize, k: isize) -> T {
+        match i.checked_add_signed(k) {
+            Some(j) if j < x.len() => a[i] * x[j],
+            _ => T::default(),
+        }
+    }
+
+    /// `*x`, read with a volatile load.
+    #[inline(always)]
+    pub fn load<T: Copy>(x: &T) -> T {
+        // (a reference is valid for reads)
+        unsafe { core::ptr::read_volatile(x) }
+    }
+
+    /// `*x = v`, written with a volatile store.
+    #[inline(always)]
+    pub fn store<T: Copy>(x: &mut T, v: T) {
+        unsafe { core::ptr::write_volatile(x, v) }
+    }
+
+    /// A compiler fence, which keeps memory accesses from being moved across it.
+    #[inline(always)]
+    pub fn fence() {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// The part of `x` in `s..e`, empty where `x` is shorter.
+    #[inline(always)]
+    pub fn tile<T>(x: &[T], s: usize, e: usize) -> &[T] {
+        let n = x.len();
+        &x[min(s, n)..min(e, n)]
+    }
+
+    #[inline(always)]
+    pub fn tile_mut<T>(x: &mut [T], s: usize, e: usize) -> &mut [T] {
+        let n = x.len();
+        &mut x[min(s, n)..min(e, n)]
+    }
+
+    /// The first `n` elements of row `i` of a panel whose rows start `ld` elements apart, fewer
+    /// where `x` ends.
+    #[inline(always)]
+    pub fn panel_row<T>(x: &[T], i: usize, n: usize, ld: usize) -> &[T] {
+        tile(x, i * ld, i * ld + n)
+    }
+
+    #[inline(always)]
+    pub fn panel_row_mut<T>(x: &mut [T], i: usize, n: usize, ld: usize) -> &mut [T] {
+        tile_mut(x, i * ld, i * ld + n)
+    }
+
+    /// Sums an iterator into its own item type, sparing the macros a type annotation.
+    #[inline(always)]
+    pub fn sum<T: core::iter::Sum<T>, I: Iterator<Item = T>>(iter: I) -> T {
+        iter.sum()
+    }
+
+    /// An integer sum converted to the type of a scale and multiplied by it, for
+    /// `dot![widen(T, s): ...]`.
+    pub trait Rescale<S> {
+        fn rescale(self, s: S) -> S;
+    }
+
+    macro_rules! rescale {
+        ($($t:ty),*; $s:ty) => {
+            $(
+                impl Rescale<$s> for $t {
+                    #[inline(always)]
+                    fn rescale(self, s: $s) -> $s {
+                        self as $s * s
+                    }
+                }
+            )*
+        };
+    }
+    rescale!(i16, i32, i64, u16, u32, u64; f32);
+    rescale!(i16, i32, i64, u16, u32, u64; f64);
+
+    /// The only value of `axpy_at!`.
+    #[inline(always)]
+    pub fn first<T, I: Iterator<Item = T>>(mut iter: I) -> T {
+        iter.next().expect("axpy_at!: no value")
+    }
+
+    /// The values of `axpy![extend(s): ...]`, added to `s`.
+    #[inline(always)]
+    pub fn extend<T, S: Extend<T>, I: Iterator<Item = T>>(s: &mut S, iter: I) {
+        s.extend(iter)
+    }
+
+    /// The values of `axpy![each(f): ...]`, passed to `f` together with their indices.
+    #[inline(always)]
+    pub fn each<T, F: FnMut(usize, T), I: Iterator<Item = T>>(f: &mut F, iter: I) {
+        for (i, v) in iter.enumerate() {
+            f(i, v)
+        }
+    }
+
+    /// Sum of absolute values, with the absolute value written as a max to keep the loop
+    /// branch-free.
+    #[inline(always)]
+    pub fn asum<T, I>(iter: I) -> T
+        where T: Copy + PartialOrd + Neg<Output = T> + core::iter::Sum<T>, I: Iterator<Item = T>
+    {
+        iter.map(|v| max(v, -v)).sum()
+    }
+
+    /// `asum` with the absolute values added up by `sum_kahan`.
+    #[inline(always)]
+    pub fn asum_kahan<T, I>(iter: I) -> T
+        where T: Copy + Default + PartialOrd + Neg<Output = T> + Add<Output = T> + Sub<Output = T>,
+              I: Iterator<Item = T>
+    {
+        sum_kahan(iter.map(|v| max(v, -v)))
+    }
+
+    /// `asum` with the absolute values added up by `sum_neumaier`.
+    #[inline(always)]
+    pub fn asum_neumaier<T, I>(iter: I) -> T
+        where T: Copy + Default + PartialOrd + Neg<Output = T> + Add<Output = T> + Sub<Output = T>,
+              I: Iterator<Item = T>
+    {
+        sum_neumaier(iter.map(|v| max(v, -v)))
+    }
+
+    /// Largest value, `None` if there are none; a NaN value makes the result NaN.
+    #[inline]
+    pub fn max_value<T: Copy + PartialOrd, I: Iterator<Item = T>>(iter: I) -> Option<T> {
+        fold_nan(iter, |v, m| v > m)
+    }
+
+    /// Smallest value, `None` if there are none; a NaN value makes the result NaN.
+    #[inline]
+    pub fn min_value<T: Copy + PartialOrd, I: Iterator<Item = T>>(iter: I) -> Option<T> {
+        fold_nan(iter, |v, m| v < m)
+    }
+
+    /// Largest value that is not NaN, `None` if there are none.
+    #[inline]
+    pub fn max_value_ignore_nan<T: Copy + PartialOrd, I: Iterator<Item = T>>(iter: I) -> Option<T> {
+        iter.filter(|v| v.partial_cmp(v).is_some())
+            .fold(None, |m, v| Some(match m { Some(m) => max(m, v), None => v }))
+    }
+
+    /// Smallest value that is not NaN, `None` if there are none.
+    #[inline]
+    pub fn min_value_ignore_nan<T: Copy + PartialOrd, I: Iterator<Item = T>>(iter: I) -> Option<T> {
+        iter.filter(|v| v.partial_cmp(v).is_some())
+            .fold(None, |m, v| Some(match m { Some(m) => min(m, v), None => v }))
+    }
+
+    #[inline(always)]
+    fn fold_nan<T, I, F>(mut iter: I, better: F) -> Option<T>
+        where T: Copy + PartialOrd, I: Iterator<Item = T>, F: Fn(T, T) -> bool
+    {
+        let mut m = iter.next()?;
+        for v in iter {
+            if m.partial_cmp(&m).is_none() { break; }
+            if better(v, m) || v.partial_cmp(&v).is_none() { m = v; }
+        }
+        Some(m)
+    }
+
+    /// Count, mean and variance of the values.
+    #[inline(always)]
+    pub fn stats<T: acc::Real, I: Iterator<Item = T>>(iter: I) -> acc::Stats<T> {
+        acc::stats(iter)
+    }
+
+    /// Position of the first value of largest magnitude, `None` if there are none. NaNs are passed
+    /// over, unless every value is NaN.
+    #[inline]
+    pub fn iamax<T, I>(iter: I) -> Option<usize>
+        where T: Copy + PartialOrd + Neg<Output = T>, I: Iterator<Item = T>
+    {
+        extremum(iter, |m, best| m > best)
+    }
+
+    /// Position of the first value of smallest magnitude, `None` if there are none.
+    #[inline]
+    pub fn iamin<T, I>(iter: I) -> Option<usize>
+        where T: Copy + PartialOrd + Neg<Output = T>, I: Iterator<Item = T>
+    {
+        extremum(iter, |m, best| m < best)
+    }
+
+    #[inline(always)]
+    fn extremum<T, I, F>(iter: I, better: F) -> Option<usize>
+        where T: Copy + PartialOrd + Neg<Output = T>, I: Iterator<Item = T>, F: Fn(T, T) -> bool
+    {
+        let mut best: Option<(usize, T)> = None;
+        for (i, v) in iter.enumerate() {
+            let m = max(v, -v);
+            match best {
+                // a NaN is incomparable, even to itself
+                Some((_, b)) if b.partial_cmp(&b).is_some() && !better(m, b) => {}
+                Some((_, b)) if b.partial_cmp(&b).is_none() && m.partial_cmp(&m).is_none() => {}
+                _ => best = Some((i, m)),
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Kahan-compensated sum: the low-order bits lost by each addition are carried along and
+    /// subtracted from the next term.
+    #[inline(always)]
+    pub fn sum_kahan<T, I>(iter: I) -> T
+        where T: Copy + Default + Add<Output = T> + Sub<Output = T>, I: Iterator<Item = T>
+    {
+        let (mut s, mut c) = (T::default(), T::default());
+        for v in iter {
+            let y = v - c;
+            let t = s + y;
+            c = (t - s) - y;
+            s = t;
+        }
+        s
+    }
+
+    /// Plain sum of `terms` products, each rounded `k` times, along with the running error bound
+    /// `u * (|s_1| + ... + |s_n| + k*(|z_1| + ... + |z_n|))` of the partial sums `s_i` and terms
+    /// `z_i` (to first order in the unit roundoff `u`, see Higham, Accuracy and Stability of
+    /// Numerical Algorithms, 3.1).
+    #[inline(always)]
+    pub fn sum_bound<T: acc::Roundoff, I: Iterator<Item = T>>(iter: I, k: usize) -> (T, T) {
+        let (mut s, mut mu, mut nu) = (T::default(), T::default(), T::default());
+        for z in iter {
+            s = s + z;
+            mu = mu + max(s, -s);
+            nu = nu + max(z, -z);
+        }
+        (s, T::unit_roundoff() * (mu + T::from_count(k) * nu))
+    }
+
+    /// Neumaier's variant of compensated summation, which also stays accurate when a term is
+    /// larger in magnitude than the running sum.
+    #[inline(always)]
+    pub fn sum_neumaier<T, I>(iter: I) -> T
+        where T: Copy + Default + PartialOrd + Neg<Output = T> + Add<Output = T> + Sub<Output = T>,
+              I: Iterator<Item = T>
+    {
+        let (mut s, mut c) = (T::default(), T::default());
+        for v in iter {
+            let t = s + v;
+            if max(s, -s) >= max(v, -v) { c = c + ((s - t) + v); } else { c = c + ((v - t) + s); }
+            s = t;
+        }
+        s + c
+    }
+
+    /// Sum of the products `x[i] * y[i]` accumulated in `K` independent lanes, product `i` going
+    /// to lane `i % K`, so that the additions of consecutive products do not wait for one another.
+    /// The slices are walked in windows of `K`, each a fixed-size loop that vectorizes, followed by
+    /// the shorter remainder.
+    #[inline(always)]
+    pub fn sum_lanes<T, const K: usize>(x: &[T], y: &[T], _lanes: [(); K]) -> T
+        where T: Copy + Default + Add<Output = T> + Mul<Output = T>
+    {
+        assert!(K > 0, "summation needs at least one lane");
+        let n = min(x.len(), y.len());
+        let (x, y) = (x[..n].chunks_exact(K), y[..n].chunks_exact(K));
+        let (xr, yr) = (x.remainder(), y.remainder());
+        let mut acc = [T::default(); K];
+        for (x, y) in x.zip(y) {
+            for (a, (x, y)) in acc.iter_mut().zip(x.iter().zip(y)) {
+                *a = *a + *x * *y;
+            }
+        }
+        for (a, (x, y)) in acc.iter_mut().zip(xr.iter().zip(yr)) {
+            *a = *a + *x * *y;
+        }
+        acc.iter().fold(T::default(), |s, &a| s + a)
+    }
+
+    /// `sum_lanes` of the products `x[i] * w[i] * y[i]`.
+    #[inline(always)]
+    pub fn sum_lanes3<T, const K: usize>(x: &[T], w: &[T], y: &[T], _lanes: [(); K]) -> T
+        where T: Copy + Default + Add<Output = T> + Mul<Output = T>
+    {
+        assert!(K > 0, "summation needs at least one lane");
+        let n = min(x.len(), min(w.len(), y.len()));
+        let (x, w, y) = (x[..n].chunks_exact(K), w[..n].chunks_exact(K), y[..n].chunks_exact(K));
+        let (xr, wr, yr) = (x.remainder(), w.remainder(), y.remainder());
+        let mut acc = [T::default(); K];
+        for (x, (w, y)) in x.zip(w.zip(y)) {
+            for (a, (x, (w, y))) in acc.iter_mut().zip(x.iter().zip(w.iter().zip(y))) {
+                *a = *a + *x * *w * *y;
+            }
+        }
+        for (a, (x, (w, y))) in acc.iter_mut().zip(xr.iter().zip(wr.iter().zip(yr))) {
+            *a = *a + *x * *w * *y;
+        }
+        acc.iter().fold(T::default(), |s, &a| s + a)
+    }
+
+    /// Pairwise (cascade) summation: blocks of `block` terms are summed left to right, and the
+    /// block sums are combined as a balanced binary tree, so the error grows as O(log n) rather
+    /// than O(n) while the inner loop stays a plain vectorizable fold.
+    #[inline]
+    pub fn sum_pairwise<T, I>(mut iter: I, block: usize) -> T
+        where T: Copy + Default + Add<Output = T>, I: Iterator<Item = T>
+    {
+        assert!(block > 0, "pairwise summation needs a positive block size");
+        // stack[i] holds the sum of 2^k blocks, with k decreasing as i increases
+        let mut stack = [T::default(); usize::BITS as usize];
+        let (mut depth, mut blocks) = (0, 0usize);
+        loop {
+            let (mut s, mut n) = (T::default(), 0);
+            for v in iter.by_ref().take(block) {
+                s = s + v;
+                n += 1;
+            }
+            if n == 0 { break; }
+            stack[depth] = s;
+            depth += 1;
+            blocks += 1;
+            let mut m = blocks;
+            while m & 1 == 0 {
+                depth -= 1;
+                stack[depth - 1] = stack[depth - 1] + stack[depth];
+                m >>= 1;
+            }
+            if n < block { break; }
+        }
+        stack[..depth].iter().rev().fold(T::default(), |acc, &v| v + acc)
+    }
+}
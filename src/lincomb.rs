@@ -0,0 +1,14 @@
+/// A linear combination built once and evaluated later, into any target.
+///
+/// `let update = lincomb![a*x + b*y];` is a closure taking the target and then the vector
+/// operands, in the order in which they first appear, as slices:
+///     update(&mut z, &x, &y);  // axpy![z = a*x + b*y]
+///
+/// The operands are thus only borrowed while the combination is evaluated, whereas the
+/// coefficients are captured (moved) when it is built. Being a closure, the combination can be
+/// stored as e.g. a `Box<dyn Fn(&mut [f64], &[f64], &[f64])>`, or passed on as an `impl Fn`, where
+/// the macro itself would be awkward.
+#[macro_export]
+macro_rules! lincomb {
+    [$($rest:tt)+] => { axpy![! (emit (lincomb) __axpy_target = ()) () $($rest)* + .] };
+}
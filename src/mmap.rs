@@ -0,0 +1,79 @@
+//! Memory-mapped operands for `axpy![mmap: ...]`.
+//!
+//! A mapped file, e.g. a `memmap2::Mmap` or `MmapMut`, is a slice of bytes; `view` and `view_mut`
+//! reinterpret it as the slice of numbers it stores (in native byte order), which is then an
+//! operand or target like any other, without copying the file into memory:
+//!     let x = mmap::view::<f64>(&x_map);
+//!     let y = mmap::view::<f64>(&y_map);
+//!     let z = mmap::view_mut::<f64>(&mut z_map);
+//!     axpy![mmap: z = a*x + y];
+//!
+//! A plain statement on such slices takes a page fault every few hundred elements of every
+//! operand in turn, and these stall the fused loop. The `mmap:` prefix instead evaluates it in
+//! chunks of `CHUNK` bytes of the target, and first reads every page of the chunk of each source
+//! and of the target, one operand at a time, so that the pages are brought in by sequential sweeps
+//! (which the kernel's read-ahead is made for) and the loop itself runs on resident memory.
+
+use core::mem::size_of;
+use core::slice;
+
+/// The size of a chunk of `axpy![mmap: ...]`, in bytes of the target.
+pub const CHUNK: usize = 1 << 21;
+
+/// The size of the pages read ahead of every chunk.
+pub const PAGE: usize = 1 << 12;
+
+/// A type of which every bit pattern is a valid value, so that it can be read from any bytes.
+///
+/// # Safety
+/// Every bit pattern of `size_of::<Self>()` bytes is a valid value of the type.
+pub unsafe trait Element: Copy {}
+
+unsafe impl Element for f32 {}
+unsafe impl Element for f64 {}
+unsafe impl Element for i8 {}
+unsafe impl Element for i16 {}
+unsafe impl Element for i32 {}
+unsafe impl Element for i64 {}
+unsafe impl Element for u8 {}
+unsafe impl Element for u16 {}
+unsafe impl Element for u32 {}
+unsafe impl Element for u64 {}
+
+/// `bytes` as a slice of `T`. Panics unless `bytes` is aligned for `T` and a whole number of
+/// elements long (a mapping is page-aligned, and so is any offset into it that is a multiple of
+/// the size of `T`).
+pub fn view<T: Element>(bytes: &[u8]) -> &[T] {
+    check::<T>(bytes);
+    unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T, bytes.len() / size_of::<T>()) }
+}
+
+/// `bytes` as a mutable slice of `T`, as for `view`.
+pub fn view_mut<T: Element>(bytes: &mut [u8]) -> &mut [T] {
+    check::<T>(bytes);
+    unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, bytes.len() / size_of::<T>()) }
+}
+
+fn check<T>(bytes: &[u8]) {
+    assert!((bytes.as_ptr() as usize).is_multiple_of(core::mem::align_of::<T>()),
+        "axpy!: mmap: the bytes are not aligned for the element type");
+    assert!(bytes.len().is_multiple_of(size_of::<T>()),
+        "axpy!: mmap: the bytes are not a whole number of elements");
+}
+
+/// The number of elements of a chunk of `x`.
+#[doc(hidden)]
+pub fn chunk_len<T>(_: &[T]) -> usize {
+    (CHUNK / size_of::<T>().max(1)).max(1)
+}
+
+/// Reads one element of every page of `x`, faulting them in in one sweep.
+#[doc(hidden)]
+#[inline]
+pub fn touch<T: Copy>(x: &[T]) {
+    let step = (PAGE / size_of::<T>().max(1)).max(1);
+    for i in (0..x.len()).step_by(step) {
+        // (a reference is valid for reads)
+        unsafe { core::ptr::read_volatile(&x[i]) };
+    }
+}
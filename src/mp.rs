@@ -0,0 +1,14 @@
+/// The `rug:` prefix, available with the `rug` feature.
+#[cfg(feature = "rug")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __axpy_rug {
+    [$y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (rug) $y $assign ()) () $($rest)* + .] };
+}
+
+#[cfg(not(feature = "rug"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __axpy_rug {
+    [$($rest:tt)+] => { compile_error!("axpy!: rug: requires the `rug` feature") };
+}
@@ -0,0 +1,163 @@
+//! Blue's scaled sum of squares for `nrm2!`, as in LAPACK's `dnrm2`.
+
+/// The floating-point types `nrm2!` is defined for.
+pub trait Real: Copy + PartialOrd {
+    /// Magnitudes below `TSML` are scaled up by `SSML` before squaring, those above `TBIG` scaled
+    /// down by `SBIG`, so that no square overflows or underflows.
+    const TSML: Self;
+    const TBIG: Self;
+    const SSML: Self;
+    const SBIG: Self;
+    const ZERO: Self;
+    const ONE: Self;
+    fn abs(self) -> Self;
+    fn is_nan(self) -> bool;
+    fn sqrt(self) -> Self;
+    fn add(self, o: Self) -> Self;
+    fn sub(self, o: Self) -> Self;
+    fn mul(self, o: Self) -> Self;
+    fn div(self, o: Self) -> Self;
+}
+
+macro_rules! real {
+    ($t:ident, $bits:ident, $mant:expr, $bias:expr, [$tsml:expr, $tbig:expr, $ssml:expr, $sbig:expr]) => {
+        impl Real for $t {
+            const TSML: $t = $t::from_bits((($bias + $tsml) as $bits) << $mant);
+            const TBIG: $t = $t::from_bits((($bias + $tbig) as $bits) << $mant);
+            const SSML: $t = $t::from_bits((($bias + $ssml) as $bits) << $mant);
+            const SBIG: $t = $t::from_bits((($bias + $sbig) as $bits) << $mant);
+            const ZERO: $t = 0.;
+            const ONE: $t = 1.;
+            #[inline(always)]
+            fn abs(self) -> $t { $t::abs(self) }
+            #[inline(always)]
+            fn is_nan(self) -> bool { $t::is_nan(self) }
+            #[inline(always)]
+            fn sqrt(self) -> $t { $t::sqrt(self) }
+            #[inline(always)]
+            fn add(self, o: $t) -> $t { self + o }
+            #[inline(always)]
+            fn sub(self, o: $t) -> $t { self - o }
+            #[inline(always)]
+            fn mul(self, o: $t) -> $t { self * o }
+            #[inline(always)]
+            fn div(self, o: $t) -> $t { self / o }
+        }
+    };
+}
+
+// the powers of two of LAPACK's constants, derived from the exponent range and precision
+real!(f32, u32, 23, 127, [-63, 52, 75, -76]);
+real!(f64, u64, 52, 1023, [-511, 486, 537, -538]);
+
+/// One of the three sums of squares of `nrm2`, plain or compensated.
+trait Sum<T: Real> {
+    fn new() -> Self;
+    fn add(&mut self, v: T);
+    fn total(&self) -> T;
+}
+
+struct Plain<T>(T);
+
+impl<T: Real> Sum<T> for Plain<T> {
+    #[inline(always)]
+    fn new() -> Self { Plain(T::ZERO) }
+    #[inline(always)]
+    fn add(&mut self, v: T) { self.0 = self.0.add(v); }
+    #[inline(always)]
+    fn total(&self) -> T { self.0 }
+}
+
+/// Kahan's compensated sum, as in `__rt::sum_kahan`.
+struct Kahan<T>(T, T);
+
+impl<T: Real> Sum<T> for Kahan<T> {
+    #[inline(always)]
+    fn new() -> Self { Kahan(T::ZERO, T::ZERO) }
+    #[inline(always)]
+    fn add(&mut self, v: T) {
+        let y = v.sub(self.1);
+        let t = self.0.add(y);
+        self.1 = t.sub(self.0).sub(y);
+        self.0 = t;
+    }
+    #[inline(always)]
+    fn total(&self) -> T { self.0 }
+}
+
+/// Neumaier's compensated sum, as in `__rt::sum_neumaier`.
+struct Neumaier<T>(T, T);
+
+impl<T: Real> Sum<T> for Neumaier<T> {
+    #[inline(always)]
+    fn new() -> Self { Neumaier(T::ZERO, T::ZERO) }
+    #[inline(always)]
+    fn add(&mut self, v: T) {
+        let t = self.0.add(v);
+        self.1 = if self.0.abs() >= v.abs() {
+            self.1.add(self.0.sub(t).add(v))
+        } else {
+            self.1.add(v.sub(t).add(self.0))
+        };
+        self.0 = t;
+    }
+    #[inline(always)]
+    fn total(&self) -> T { self.0.add(self.1) }
+}
+
+/// The Euclidean norm of `x`, accumulating small, medium and large magnitudes separately in one
+/// branch-light pass and combining the three sums at the end.
+#[inline]
+pub fn nrm2<T: Real>(x: &[T]) -> T {
+    scaled::<T, Plain<T>>(x)
+}
+
+/// `nrm2` with each of the three sums of squares Kahan-compensated.
+#[inline]
+pub fn nrm2_kahan<T: Real>(x: &[T]) -> T {
+    scaled::<T, Kahan<T>>(x)
+}
+
+/// `nrm2` with each of the three sums of squares compensated as by Neumaier.
+#[inline]
+pub fn nrm2_neumaier<T: Real>(x: &[T]) -> T {
+    scaled::<T, Neumaier<T>>(x)
+}
+
+#[inline(always)]
+fn scaled<T: Real, S: Sum<T>>(x: &[T]) -> T {
+    let (mut sml, mut med, mut big) = (S::new(), S::new(), S::new());
+    for &v in x {
+        let a = v.abs();
+        if a > T::TBIG {
+            let s = a.mul(T::SBIG);
+            big.add(s.mul(s));
+        } else if a < T::TSML {
+            let s = a.mul(T::SSML);
+            sml.add(s.mul(s));
+        } else {
+            med.add(a.mul(a));
+        }
+    }
+    let (asml, amed, mut abig) = (sml.total(), med.total(), big.total());
+    // (a NaN in the medium sum must survive the combination)
+    let (scale, sumsq) = if abig > T::ZERO {
+        if amed > T::ZERO || amed.is_nan() {
+            abig = abig.add(amed.mul(T::SBIG).mul(T::SBIG));
+        }
+        (T::ONE.div(T::SBIG), abig)
+    } else if asml > T::ZERO {
+        if amed > T::ZERO || amed.is_nan() {
+            let amed = amed.sqrt();
+            let asml = asml.sqrt().div(T::SSML);
+            let (lo, hi) = if asml > amed { (amed, asml) } else { (asml, amed) };
+            let r = lo.div(hi);
+            (T::ONE, hi.mul(hi).mul(T::ONE.add(r.mul(r))))
+        } else {
+            (T::ONE.div(T::SSML), asml)
+        }
+    } else {
+        (T::ONE, amed)
+    };
+    scale.mul(sumsq.sqrt())
+}
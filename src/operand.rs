@@ -0,0 +1,26 @@
+/// A container that `axpy!` (and the other macros) can read from.
+///
+/// The expansions call `x.iter()` on every source, so slices, arrays, `Vec` and anything else with
+/// an inherent `iter` method work as they are. Containers without one (arenas, pinned buffers,
+/// ...) can implement this trait instead, which is in scope inside every expansion.
+///
+/// Containers that dereference to a slice, such as `SmallVec`, `ArrayVec` or `TinyVec`, need
+/// neither: `iter`, `iter_mut` and `len` resolve to the slice methods through `Deref`, inline or
+/// spilled alike. The same goes for the smart pointers `Box<[T]>`, `Rc<[T]>`, `Arc<[T]>` and
+/// `Cow<[T]>` (the latter two as sources only), e.g. an `Arc<[T]>` cloned into worker threads.
+///
+/// There are no features for these crates, and nothing special is generated for inline storage:
+/// an inline `SmallVec<[f32; 16]>` goes through the same loop as a spilled one, its length known
+/// only at run time, so no unrolled code is generated for it.
+pub trait AxpySource<'a> {
+    /// An iterator over references to the elements.
+    type Iter: Iterator;
+    fn iter(&'a self) -> Self::Iter;
+}
+
+/// A container that `axpy!` (and the other macros) can assign to, see `AxpySource`.
+pub trait AxpyTarget<'a> {
+    /// An iterator over mutable references to the elements.
+    type IterMut: Iterator;
+    fn iter_mut(&'a mut self) -> Self::IterMut;
+}
@@ -0,0 +1,11 @@
+/// Evaluates a linear combination into a freshly allocated `Vec` (requires the `alloc` feature).
+///
+/// `let z = axpy_new![a*x + y];` takes the same right-hand sides as `axpy!`, and becomes
+///     x.iter().zip(y.iter()).map(|(x, y)| a * *x + *y).collect::<Vec<_>>()
+///
+/// Since the zipped slice iterators report an exact length, the vector is allocated once and
+/// filled in a single pass, without zeroing it first. Its length is that of the shortest operand.
+#[macro_export]
+macro_rules! axpy_new {
+    [$($rest:tt)+] => { axpy![! (emit (new) __axpy_new = ()) () $($rest)* + .] };
+}
@@ -0,0 +1,64 @@
+/// Elementwise polynomial evaluation by Horner's scheme.
+///
+/// `polyval![y = c0 + c1*x + c2*x^2 + c3*x^3]` becomes
+///     for (y, x) in y.iter_mut().zip(x.iter()) {
+///         *y = c0 + *x * (c1 + *x * (c2 + *x * c3));
+///     }
+///
+/// The terms must be written in ascending powers without gaps (this is checked at compile time),
+/// and `y` and `x` may be the same vector. With the `fma:` prefix, each step is a `mul_add` instead,
+/// i.e. `x.mul_add(x.mul_add(x.mul_add(c3, c2), c1), c0)`, which needs the inherent float methods
+/// from `std`.
+#[macro_export]
+macro_rules! polyval {
+    [fma: $y:ident = $($rest:tt)+] => { polyval![! (fma) $y = $($rest)+] };
+    [$y:ident = $($rest:tt)+] => { polyval![! () $y = $($rest)+] };
+
+    // collect the coefficients (ascending) and check every term is in the same variable and power
+    [! $f:tt $y:ident = $c0:tt + $c1:tt * $x:ident $(+ $c:tt * $xk:ident ^ $k:tt)*] => {
+        {
+            $(
+                macro_rules! eval {
+                    ($x $x) => {};
+                    ($xk $x) => { compile_error!("polyval!: all terms must be powers of the same variable") };
+                }
+                eval!($xk $x);
+            )*
+            polyval![? (1 1) $($k)*];
+            polyval![@ $f $y $x; $c0 $c1 $($c)*]
+        }
+    };
+
+    // powers must count up from 2
+    [? ($($n:tt)*)] => {};
+    [? ($($n:tt)*) $k:tt $($rest:tt)*] => {
+        const _: () = assert!($k == 0 $(+ $n)*, "polyval!: powers must ascend without gaps");
+        polyval![? (1 $($n)*) $($rest)*];
+    };
+
+    // the loop, where x may be the assigned vector
+    [@ $f:tt $y:ident $x:ident; $($c:tt)+] => {
+        {
+            macro_rules! eval {
+                ($y $y) => {
+                    for car in $y.iter_mut() {
+                        let x = *car;
+                        *car = polyval![# $f x; $($c)+];
+                    }
+                };
+                ($x $y) => {
+                    for (car, cdr) in $y.iter_mut().zip($x.iter()) {
+                        let x = *cdr;
+                        *car = polyval![# $f x; $($c)+];
+                    }
+                };
+            }
+            eval!($x $y)
+        }
+    };
+
+    // Horner's scheme: c_k + x * (c_{k+1} + x * (...)), or the nested mul_add equivalent
+    [# $f:tt $x:ident; $c:tt] => { $c };
+    [# () $x:ident; $c:tt $($rest:tt)+] => { $c + $x * polyval![# () $x; $($rest)+] };
+    [# (fma) $x:ident; $c:tt $($rest:tt)+] => { $x.mul_add(polyval![# (fma) $x; $($rest)+], $c) };
+}
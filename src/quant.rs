@@ -0,0 +1,64 @@
+//! Quantized tensors as operands and targets of `quant:` statements.
+//!
+//! An 8-bit quantized tensor stores `q` for the real value `scale * (q - zero)`, with one scale
+//! and zero point for the whole tensor. `axpy![quant: z = x + 0.5*y]`, with `Quant` operands and
+//! target, computes every element in `f32` from the dequantized elements of the operands, then
+//! requantizes it to the target's own scale and zero point (rounding to nearest, and saturating)
+//! as it is stored. Coefficients are `f32`, e.g. for bias-add and residual-add layers.
+
+/// An integer type that quantized values are stored in, i.e. `i8` or `u8`.
+pub trait Q8: Copy {
+    fn to_i32(self) -> i32;
+    /// `q`, saturated to the range of the type.
+    fn saturate(q: i32) -> Self;
+}
+
+impl Q8 for i8 {
+    #[inline(always)]
+    fn to_i32(self) -> i32 { self as i32 }
+    #[inline(always)]
+    fn saturate(q: i32) -> i8 { q.clamp(i8::MIN as i32, i8::MAX as i32) as i8 }
+}
+
+impl Q8 for u8 {
+    #[inline(always)]
+    fn to_i32(self) -> i32 { self as i32 }
+    #[inline(always)]
+    fn saturate(q: i32) -> u8 { q.clamp(0, u8::MAX as i32) as u8 }
+}
+
+/// A quantized tensor: its data (a slice, mutable slice or `Vec` of `i8` or `u8`) with the scale
+/// and zero point of the whole.
+#[derive(Clone, Debug)]
+pub struct Quant<S> {
+    pub data: S,
+    pub scale: f32,
+    pub zero: i32,
+}
+
+impl<S> Quant<S> {
+    pub fn new(data: S, scale: f32, zero: i32) -> Quant<S> {
+        Quant { data, scale, zero }
+    }
+
+    /// The real value of the stored `q`.
+    #[inline(always)]
+    pub fn dequantize<T: Q8>(&self, q: T) -> f32 {
+        (q.to_i32() - self.zero) as f32 * self.scale
+    }
+
+    /// The stored value nearest to the real `v`, saturated.
+    #[inline(always)]
+    pub fn quantize<T: Q8>(&self, v: f32) -> T {
+        let t = v / self.scale;
+        // (`as` truncates towards zero, and saturates)
+        let q = if t < 0. { t - 0.5 } else { t + 0.5 } as i32;
+        T::saturate(q.saturating_add(self.zero))
+    }
+
+    /// The real values of the elements.
+    #[inline(always)]
+    pub fn iter<'a, T: Q8 + 'a>(&'a self) -> impl Iterator<Item = f32> + 'a where S: AsRef<[T]> {
+        self.data.as_ref().iter().map(move |&q| self.dequantize(q))
+    }
+}
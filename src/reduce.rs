@@ -0,0 +1,202 @@
+/// Inner product of two slice-like objects, fused into a single pass.
+///
+/// `dot![x, y]` becomes
+///     x.iter().zip(y.iter()).map(|(x, y)| *x * *y).sum()
+///
+/// with the sum taken in the element type, so no annotation is needed at the call site. As with
+/// `axpy!`, mismatched lengths are silently truncated to the shorter operand.
+///
+/// The weighted form `dot![x, w, y]` computes the sum of `x[i] * w[i] * y[i]` in the same single
+/// pass, e.g. for weighted norms or mass-matrix inner products.
+///
+/// The arguments may also be any combinations, as on the right-hand side of `axpy!`, e.g.
+/// `dot![r - a*q, r - a*q]` or `dot![a*x + y, z - b*w]`, which are evaluated element by element
+/// in the same single pass, without storing either side.
+///
+/// For long floating-point reductions, either form may be prefixed with `kahan:` or `neumaier:` to
+/// carry a compensation term alongside the running sum, e.g. `dot![kahan: x, y]`. This costs a few
+/// extra flops per element (and the element type must implement `Default` as its zero) but keeps
+/// the error essentially independent of the length. `asum!` and `nrm2!` take the same prefixes.
+///
+/// Alternatively, `pairwise:` sums blocks of 64 products naively and combines the block sums as a
+/// balanced tree, giving O(log n) error growth while the per-block loop still vectorizes. The block
+/// size can be chosen explicitly, e.g. `dot![pairwise(256): x, y]`.
+///
+/// For verified numerics, `bound:` returns the plain sum together with an estimate of its absolute
+/// error, `let (d, err) = dot![bound: x, y];`, accumulated alongside it from the magnitudes of the
+/// products and of the partial sums (a running error bound, valid to first order in the unit
+/// roundoff of `acc::Roundoff`).
+///
+/// Plain sums are latency-bound, every addition waiting for the previous one. `lanes:` instead
+/// accumulates consecutive products into 8 independent partial sums, added up at the end, so that
+/// as many additions are in flight at once; `lanes(k):` uses `k` of them, e.g. `dot![lanes(4): x, y]`.
+/// (The result may then differ from the plain sum in the last bits, the order of the additions
+/// being different.)
+///
+/// Integer inputs, e.g. the `i8` weights and activations of a quantized network or `i16` samples,
+/// are better multiplied and summed in a wider type, which `widen(T):` names, e.g.
+/// `dot![widen(i32): w, x]` converts every element to `i32` (losslessly, by `From`) before
+/// multiplying. `widen(T, s):` then converts the sum to the type of the scale `s`, `f32` or `f64`,
+/// and multiplies it by `s`, e.g. `dot![widen(i32, sw * sx): w, x]` for the product of two
+/// quantization scales.
+///
+/// Data that arrives in chunks can be reduced into a streaming accumulator, see `acc::DotAcc`:
+/// `dot![acc += x, y]` adds the inner product of the current chunks to `acc`.
+///
+/// Per-block results, e.g. the energy of every segment of a signal, are written into a slice by
+/// `dot![blocks(b, out): x, x]`, which stores the inner product of the `k`-th block of `b`
+/// elements in `out[k]` (the last block may be shorter, and the blocks that do not fit in `out`
+/// are left out). `asum!`, `max!` and `min!` take the same prefix.
+#[macro_export]
+macro_rules! dot {
+    [$acc:ident += $($args:tt)+] => { $acc.update(dot![$($args)+]) };
+    [blocks($b:expr, $out:ident): $x:ident, $w:ident, $y:ident] => {
+        dot![@blocks $b, $out, $crate::__rt::min($x.len(), $crate::__rt::min($w.len(), $y.len())); s e;
+             $x[s..e].iter().zip($w[s..e].iter().zip($y[s..e].iter())).map(|(x, (w, y))| *x * *w * *y)]
+    };
+    [blocks($b:expr, $out:ident): $x:ident, $y:ident] => {
+        dot![@blocks $b, $out, $crate::__rt::min($x.len(), $y.len()); s e;
+             $x[s..e].iter().zip($y[s..e].iter()).map(|(x, y)| *x * *y)]
+    };
+    // (the block's products, in terms of its bounds s..e)
+    [@blocks $b:expr, $out:ident, $n:expr; $s:ident $e:ident; $products:expr] => {
+        {
+            let (b, n): (usize, usize) = ($b, $n);
+            assert!(b > 0, "dot!: the block size must be positive");
+            for (k, out) in $out.iter_mut().enumerate() {
+                let $s = k.saturating_mul(b);
+                if $s >= n {
+                    break;
+                }
+                let $e = $crate::__rt::min(n, $s.saturating_add(b));
+                *out = $crate::__rt::sum($products);
+            }
+        }
+    };
+    [kahan: $($args:ident),+] => { dot![@ (sum_kahan); $($args),+] };
+    [neumaier: $($args:ident),+] => { dot![@ (sum_neumaier); $($args),+] };
+    [bound: $x:ident, $y:ident] => { dot![@ (sum_bound, 1); $x, $y] };
+    [bound: $x:ident, $w:ident, $y:ident] => { dot![@ (sum_bound, 2); $x, $w, $y] };
+    [widen($t:ty): $x:ident, $w:ident, $y:ident] => {
+        $crate::__rt::sum($x.iter().zip($w.iter().zip($y.iter())).map(|(x, (w, y))|
+            <$t as From<_>>::from(*x) * <$t as From<_>>::from(*w) * <$t as From<_>>::from(*y)))
+    };
+    [widen($t:ty): $x:ident, $y:ident] => {
+        $crate::__rt::sum($x.iter().zip($y.iter()).map(|(x, y)| <$t as From<_>>::from(*x) * <$t as From<_>>::from(*y)))
+    };
+    [widen($t:ty, $s:expr): $($args:ident),+] => { $crate::__rt::Rescale::rescale(dot![widen($t): $($args),+], $s) };
+    [pairwise: $($args:ident),+] => { dot![@ (sum_pairwise, 64); $($args),+] };
+    [pairwise($b:expr): $($args:ident),+] => { dot![@ (sum_pairwise, $b); $($args),+] };
+    [lanes: $($args:ident),+] => { dot![lanes(8): $($args),+] };
+    [lanes($k:expr): $x:ident, $w:ident, $y:ident] => { $crate::__rt::sum_lanes3(&$x[..], &$w[..], &$y[..], [(); $k]) };
+    [lanes($k:expr): $x:ident, $y:ident] => { $crate::__rt::sum_lanes(&$x[..], &$y[..], [(); $k]) };
+    [$($args:ident),+] => { dot![@ (sum); $($args),+] };
+
+    // the summation strategy is one of the `__rt::sum*` functions, plus any extra arguments
+    [@ ($s:ident $(, $p:expr)*); $x:ident, $w:ident, $y:ident] => {
+        $crate::__rt::$s($x.iter().zip($w.iter().zip($y.iter())).map(|(x, (w, y))| *x * *w * *y) $(, $p)*)
+    };
+    [@ ($s:ident $(, $p:expr)*); $x:ident, $y:ident] => {
+        $crate::__rt::$s($x.iter().zip($y.iter()).map(|(x, y)| *x * *y) $(, $p)*)
+    };
+
+    // combinations, multiplied as the outer function of axpy!
+    [kahan: $($args:tt)+] => { axpy![! (emit (fold sum_kahan) __axpy_dot = (mul)) () $($args)* + .] };
+    [neumaier: $($args:tt)+] => { axpy![! (emit (fold sum_neumaier) __axpy_dot = (mul)) () $($args)* + .] };
+    [$($args:tt)+] => { axpy![! (emit (fold sum) __axpy_dot = (mul)) () $($args)* + .] };
+}
+
+/// Conjugated inner product of two complex slices, `sum(conj(x[i]) * y[i])`, in a single pass.
+///
+/// The elements implement `complex::Complex` (see `axpy![complex: ...]`), and, as there is no
+/// generic way to build one, the result is returned as its parts `[re, im]`, e.g.
+///     let [re, im] = dotc![x, y];
+///
+/// The products are spelled out in real arithmetic, so the loop vectorizes as well as `dot!`'s.
+#[macro_export]
+macro_rules! dotc {
+    [$x:ident, $y:ident] => { $crate::__rt::dotc($crate::__rt::pairs(&$x[..]), $crate::__rt::pairs(&$y[..])) };
+}
+
+/// Euclidean norm of a floating-point slice that neither overflows nor underflows in the squares.
+///
+/// `nrm2![x]` is `sqrt(dot![x, x])` computed like LAPACK's `dnrm2`: magnitudes that are very
+/// large or very small are scaled by a power of two before squaring and accumulated separately,
+/// in the same single pass, so that e.g. the norm of `[1e200, 1e200]` is `1.41e200` rather than
+/// infinity. Requires the `std` feature, for `sqrt`.
+///
+/// As for `dot!`, the prefixes `kahan:` and `neumaier:` compensate the sums of squares, e.g.
+/// `nrm2![kahan: x]`.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! nrm2 {
+    [kahan: $x:ident] => { $crate::__rt::nrm2_kahan(&$x[..]) };
+    [neumaier: $x:ident] => { $crate::__rt::nrm2_neumaier(&$x[..]) };
+    [$x:ident] => { $crate::__rt::nrm2(&$x[..]) };
+}
+
+/// Sum of absolute values of a slice, or of any right-hand side of `axpy!`, in a single pass.
+///
+/// `asum![x]` is the BLAS-1 `asum`, while e.g. `asum![x - y]` is the L1 distance of two slices,
+///     x.iter().zip(y.iter()).map(|(x, y)| *x - *y).map(|v| max(v, -v)).sum()
+///
+/// without materializing the difference. As for `dot!`, the sum may be compensated by the prefix
+/// `kahan:` or `neumaier:`, e.g. `asum![kahan: x - y]`.
+#[macro_export]
+macro_rules! asum {
+    [blocks($b:expr, $out:ident): $($rest:tt)+] =>
+        { { let b: usize = $b; axpy![! (emit (blocks b $out asum) __axpy_asum = ()) () $($rest)* + .] } };
+    [kahan: $($rest:tt)+] => { axpy![! (emit (fold asum_kahan) __axpy_asum = ()) () $($rest)* + .] };
+    [neumaier: $($rest:tt)+] => { axpy![! (emit (fold asum_neumaier) __axpy_asum = ()) () $($rest)* + .] };
+    [$($rest:tt)+] => { axpy![! (emit (fold asum) __axpy_asum = ()) () $($rest)* + .] };
+}
+
+/// Index of the element of largest magnitude of a slice, or of any right-hand side of `axpy!`.
+///
+/// `iamax![x]` is the BLAS-1 `iamax`, and `iamax![x - a*y]` finds e.g. a pivot among updated
+/// values in the same pass that computes them. The result is `None` for empty operands; ties go
+/// to the lowest index, and NaNs are passed over unless every value is NaN.
+#[macro_export]
+macro_rules! iamax {
+    [$($rest:tt)+] => { axpy![! (emit (fold iamax) __axpy_iamax = ()) () $($rest)* + .] };
+}
+
+/// Index of the element of smallest magnitude, the counterpart of `iamax!`.
+#[macro_export]
+macro_rules! iamin {
+    [$($rest:tt)+] => { axpy![! (emit (fold iamin) __axpy_iamin = ()) () $($rest)* + .] };
+}
+
+/// Largest value of a slice, or of any right-hand side of `axpy!`, or `None` if it is empty.
+///
+/// `max![abs(x - y)]` is e.g. the infinity-norm of a difference, in a single pass. A NaN value
+/// propagates to the result, unless the reduction is prefixed with `ignore_nan:`, in which case
+/// NaNs are skipped (and only NaNs give `None`). With the prefix `blocks(b, out):`, as for `dot!`,
+/// the maximum of every block is written into `out`, a slice of `Option`s.
+#[macro_export]
+macro_rules! max {
+    [blocks($b:expr, $out:ident): $($rest:tt)+] =>
+        { { let b: usize = $b; axpy![! (emit (blocks b $out max_value) __axpy_max = ()) () $($rest)* + .] } };
+    [ignore_nan: $($rest:tt)+] => { axpy![! (emit (fold max_value_ignore_nan) __axpy_max = ()) () $($rest)* + .] };
+    [$($rest:tt)+] => { axpy![! (emit (fold max_value) __axpy_max = ()) () $($rest)* + .] };
+}
+
+/// Smallest value, the counterpart of `max!`.
+#[macro_export]
+macro_rules! min {
+    [blocks($b:expr, $out:ident): $($rest:tt)+] =>
+        { { let b: usize = $b; axpy![! (emit (blocks b $out min_value) __axpy_min = ()) () $($rest)* + .] } };
+    [ignore_nan: $($rest:tt)+] => { axpy![! (emit (fold min_value_ignore_nan) __axpy_min = ()) () $($rest)* + .] };
+    [$($rest:tt)+] => { axpy![! (emit (fold min_value) __axpy_min = ()) () $($rest)* + .] };
+}
+
+/// Count, mean and variance of a slice, or of any right-hand side of `axpy!`, in one pass.
+///
+/// `let s = stats![x - y];` gives an `acc::Stats` with `s.count()`, `s.mean()`, `s.variance()` and
+/// `s.sample_variance()`, computed with Welford's update in several interleaved lanes. Chunked data
+/// is accumulated with `stats![acc += x]`, where `acc` is an `acc::Stats`.
+#[macro_export]
+macro_rules! stats {
+    [$acc:ident += $($rest:tt)+] => { $acc.merge(stats![$($rest)*]) };
+    [$($rest:tt)+] => { axpy![! (emit (fold stats) __axpy_stats = ()) () $($rest)* + .] };
+}
@@ -0,0 +1,58 @@
+//! Ring buffers, stored as at most two contiguous segments, as operands of `ring:` statements.
+
+/// A sequence stored as two contiguous slices, the elements of the first followed by those of the
+/// second, as returned by `VecDeque::as_slices`.
+///
+/// `axpy![ring: y = a*x + y]` evaluates the statement on the contiguous runs that all of its
+/// operands have in common (at most three for two rings with different heads), so that every run
+/// is an ordinary fused loop over slices and the ring never needs to be made contiguous. Slices,
+/// arrays and `Vec`s are rings with an empty second segment, and may be mixed with proper rings.
+pub trait Ring<T> {
+    fn as_slices(&self) -> (&[T], &[T]);
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]);
+
+    /// The number of elements contiguous with the `s`th, inclusive (0 past the end).
+    #[inline(always)]
+    fn run(&self, s: usize) -> usize {
+        let (a, b) = self.as_slices();
+        if s < a.len() {
+            a.len() - s
+        } else {
+            (a.len() + b.len()).saturating_sub(s)
+        }
+    }
+
+    /// The elements `s..e`, which must be contiguous.
+    #[inline(always)]
+    fn segment(&self, s: usize, e: usize) -> &[T] {
+        let (a, b) = self.as_slices();
+        let k = a.len();
+        if s < k { &a[s..e] } else { &b[s - k..e - k] }
+    }
+
+    #[inline(always)]
+    fn segment_mut(&mut self, s: usize, e: usize) -> &mut [T] {
+        let (a, b) = self.as_mut_slices();
+        let k = a.len();
+        if s < k { &mut a[s..e] } else { &mut b[s - k..e - k] }
+    }
+}
+
+impl<T> Ring<T> for [T] {
+    fn as_slices(&self) -> (&[T], &[T]) {
+        (self, &[])
+    }
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        (self, &mut [])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Ring<T> for ::alloc::collections::VecDeque<T> {
+    fn as_slices(&self) -> (&[T], &[T]) {
+        ::alloc::collections::VecDeque::as_slices(self)
+    }
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        ::alloc::collections::VecDeque::as_mut_slices(self)
+    }
+}
@@ -0,0 +1,85 @@
+//! Vector-length-agnostic RVV kernels for `axpy![riscv64: ...]`, for the RISC-V boards on which
+//! the compilers hardly auto-vectorize at all.
+//!
+//! `core::arch` has no RVV intrinsics yet, so the kernels are written with inline assembly: every
+//! strip of the loop asks `vsetvli` how many elements fit in a group of four vector registers at
+//! the hardware's vector length, which is thus only known at run time, and the last strip is
+//! simply a shorter one. The terms are applied to a strip one after the other, so it stays in the
+//! first-level cache. Requires the `v` target feature at compile time.
+//!
+//! As with the other architectures, the kernels are opt-in, by the `riscv64:` prefix, for sums of
+//! scaled `f32` or `f64` slices assigned by `=`, `+=` or `-=`; the macro does not know the element
+//! type of an unprefixed statement, which therefore keeps the generic loop, as do the reductions.
+
+use core::arch::asm;
+use core::cmp::min;
+
+/// An element type with RVV kernels, i.e. `f32` or `f64`.
+pub trait Simd: Copy {
+    /// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]`, or `y += ...` if `accumulate`, over the shortest
+    /// length.
+    fn combine<const N: usize>(y: &mut [Self], accumulate: bool, a: [Self; N], x: [&[Self]; N]);
+}
+
+/// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]` (or `y += ...`), see `Simd::combine`.
+#[inline]
+pub fn combine<T: Simd, const N: usize>(y: &mut [T], accumulate: bool, a: [T; N], x: [&[T]; N]) {
+    T::combine(y, accumulate, a, x)
+}
+
+macro_rules! simd {
+    ($t:ident, $sew:tt, $load:tt, $store:tt) => {
+        impl Simd for $t {
+            #[inline]
+            fn combine<const N: usize>(y: &mut [$t], accumulate: bool, a: [$t; N], x: [&[$t]; N]) {
+                let n = x.iter().fold(y.len(), |n, x| min(n, x.len()));
+                if N == 0 {
+                    return;
+                }
+                let mut i = 0;
+                while i < n {
+                    let mut vl: usize = 0;
+                    for k in 0..N {
+                        let (yp, xp) = unsafe { (y.as_mut_ptr().add(i), x[k].as_ptr().add(i)) };
+                        if accumulate || k > 0 {
+                            // y += a*x
+                            unsafe {
+                                asm!(
+                                    concat!("vsetvli {vl}, {n}, ", $sew, ", m4, ta, ma"),
+                                    concat!($load, " v8, ({y})"),
+                                    concat!($load, " v16, ({x})"),
+                                    "vfmacc.vf v8, {a}, v16",
+                                    concat!($store, " v8, ({y})"),
+                                    vl = out(reg) vl, n = in(reg) n - i, y = in(reg) yp, x = in(reg) xp,
+                                    a = in(freg) a[k],
+                                    out("v8") _, out("v9") _, out("v10") _, out("v11") _,
+                                    out("v16") _, out("v17") _, out("v18") _, out("v19") _,
+                                    options(nostack),
+                                );
+                            }
+                        } else {
+                            // y = a*x
+                            unsafe {
+                                asm!(
+                                    concat!("vsetvli {vl}, {n}, ", $sew, ", m4, ta, ma"),
+                                    concat!($load, " v16, ({x})"),
+                                    "vfmul.vf v8, v16, {a}",
+                                    concat!($store, " v8, ({y})"),
+                                    vl = out(reg) vl, n = in(reg) n - i, y = in(reg) yp, x = in(reg) xp,
+                                    a = in(freg) a[k],
+                                    out("v8") _, out("v9") _, out("v10") _, out("v11") _,
+                                    out("v16") _, out("v17") _, out("v18") _, out("v19") _,
+                                    options(nostack),
+                                );
+                            }
+                        }
+                    }
+                    i += vl;
+                }
+            }
+        }
+    };
+}
+
+simd!(f32, "e32", "vle32.v", "vse32.v");
+simd!(f64, "e64", "vle64.v", "vse64.v");
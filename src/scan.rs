@@ -0,0 +1,47 @@
+/// First-order linear recurrence, e.g. a one-pole IIR filter or exponential moving average.
+///
+/// `let s = scan![z = a*z + b*x; s0];` computes `z[i] = a*z[i-1] + b*x[i]`, where `z` on the
+/// right-hand side stands for the *previous* output and `s0` for the one before `z[0]`, and returns
+/// the last output so that a long signal can be filtered block by block. The remaining terms may
+/// be any `axpy!` combination (where `z` again means the current element, before the update). They
+/// are evaluated first, in one vectorized pass, and the inherently serial recurrence
+///     s = a*s + z[i]; z[i] = s;
+/// then runs over the result, unrolled by four.
+#[macro_export]
+macro_rules! scan {
+    [$z:ident = $a:tt * $y:ident $($rest:tt)+] => { scan![@ $z $a $y () $($rest)*] };
+    [@ $z:ident $a:tt $y:ident ($($e:tt)+) ; $s:expr] => {
+        {
+            macro_rules! eval {
+                ($z $z) => {
+                    {
+                        axpy![$z = $($e)*];
+                        $crate::__rt::recur(&mut $z[..], $a, $s)
+                    }
+                };
+                ($y $z) => { compile_error!("scan!: the leading term must scale the assigned vector") };
+            }
+            eval!($y $z)
+        }
+    };
+    [@ $z:ident $a:tt $y:ident ($($e:tt)*) $t:tt $($rest:tt)*] => { scan![@ $z $a $y ($($e)* $t) $($rest)*] };
+}
+
+/// Cumulative sum, e.g. integrating a sampled signal.
+///
+/// `cumsum![y = x]` computes `y[i] = x[0] + ... + x[i]`, and `cumsum![x]` does the same in place.
+/// Given a starting sum, `let s = cumsum![y = x; s0];` computes `y[i] = s0 + x[0] + ... + x[i]`
+/// and returns the last sum (`s0` if there are no elements), so that a long signal can be summed
+/// block by block as with `scan!`. The sums are taken in blocks of eight elements, each summed in
+/// three vectorizable steps (adding every element to the one 1, 2 and 4 places on) and then offset
+/// by the sum so far, so that floating-point results may differ in the last bits from those of a
+/// serial loop.
+#[macro_export]
+macro_rules! cumsum {
+    [$y:ident = $x:ident; $s:expr] => {
+        { let s = $s; $crate::__rt::cumsum(&mut $y[..], Some(&$x[..]), Some(s)).unwrap_or(s) }
+    };
+    [$y:ident = $x:ident] => { { $crate::__rt::cumsum(&mut $y[..], Some(&$x[..]), None); } };
+    [$x:ident; $s:expr] => { { let s = $s; $crate::__rt::cumsum(&mut $x[..], None, Some(s)).unwrap_or(s) } };
+    [$x:ident] => { { $crate::__rt::cumsum(&mut $x[..], None, None); } };
+}
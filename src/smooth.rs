@@ -0,0 +1,56 @@
+//! Coefficients that glide to a new value over a frame, for block-based real-time processing.
+
+use acc::Real;
+
+/// A coefficient that moves linearly from its current value to a target over the next frame,
+/// rather than jumping (which, for e.g. an audio gain, is heard as a click).
+///
+/// Every frame asks for the ramp once and uses it as a coefficient indexed by the loop index, so
+/// the interpolation happens inside the fused loop:
+///     let g = gain.frame(out.len());
+///     axpy![out[i] = (g.at(i))*inp + mix*aux];
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ramp<T> {
+    current: T,
+    target: T,
+}
+
+impl<T: Real> Ramp<T> {
+    /// A coefficient resting at `value`.
+    pub fn new(value: T) -> Ramp<T> {
+        Ramp { current: value, target: value }
+    }
+
+    /// Sets the value to reach by the end of the next frame.
+    pub fn set(&mut self, target: T) {
+        self.target = target;
+    }
+
+    /// The value reached at the end of the last frame.
+    pub fn value(&self) -> T {
+        self.current
+    }
+
+    /// The coefficients of the next frame of `n` elements, the last of which is the target.
+    pub fn frame(&mut self, n: usize) -> Frame<T> {
+        let step = (self.target - self.current) / T::from_count(n.max(1));
+        let frame = Frame { start: self.current, step };
+        self.current = self.target;
+        frame
+    }
+}
+
+/// The coefficients of one frame of a `Ramp`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frame<T> {
+    start: T,
+    step: T,
+}
+
+impl<T: Real> Frame<T> {
+    /// The coefficient of element `i`.
+    #[inline(always)]
+    pub fn at(self, i: usize) -> T {
+        self.start + self.step * T::from_count(i + 1)
+    }
+}
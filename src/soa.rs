@@ -0,0 +1,41 @@
+//! Struct-of-arrays states, updated field by field by `axpy_soa!`.
+
+/// A struct of parallel slice-like fields of one element type, seen as an array of slices.
+///
+/// Implemented by `soa!`, e.g. for
+///     struct State { x: Vec<f64>, y: Vec<f64>, vx: Vec<f64>, vy: Vec<f64> }
+///     soa!(State: f64 { x, y, vx, vy });
+/// after which `axpy_soa![state2 = state1 + dt*deriv]` runs `axpy!` on every field in turn.
+pub trait Soa<'a> {
+    type Fields;
+    type FieldsMut;
+    fn fields(&'a self) -> Self::Fields;
+    fn fields_mut(&'a mut self) -> Self::FieldsMut;
+}
+
+/// Implements `Soa` for a struct, given its element type and the fields to update.
+#[macro_export]
+macro_rules! soa {
+    ($t:ty: $e:ty { $($f:ident),+ $(,)* }) => {
+        impl<'a> $crate::soa::Soa<'a> for $t {
+            type Fields = [&'a [$e]; 0 $(+ soa!(@one $f))+];
+            type FieldsMut = [&'a mut [$e]; 0 $(+ soa!(@one $f))+];
+            fn fields(&'a self) -> Self::Fields {
+                [$(&self.$f[..]),+]
+            }
+            fn fields_mut(&'a mut self) -> Self::FieldsMut {
+                [$(&mut self.$f[..]),+]
+            }
+        }
+    };
+    (@one $f:ident) => { 1 };
+}
+
+/// Evaluates a linear combination of struct-of-arrays states field by field.
+///
+/// `axpy_soa![state2 = state1 + dt*deriv]`, for types implementing `Soa` (see `soa!`), becomes one
+/// fused loop per field, i.e. `axpy![state2.x = state1.x + dt*deriv.x]` and so on.
+#[macro_export]
+macro_rules! axpy_soa {
+    [$y:ident $assign:tt $($rest:tt)+] => { axpy![! (emit (soa) $y $assign ()) () $($rest)* + .] };
+}
@@ -0,0 +1,81 @@
+//! Stochastic rounding of low-precision targets, for `stochastic(rng):` statements.
+//!
+//! Rounding to nearest loses every update smaller than half the spacing of the stored values, so
+//! that e.g. `w -= lr*g` on `bf16` weights may leave them unchanged forever. Rounding up or down at
+//! random instead, up with a probability proportional to the distance from the value below, makes
+//! the stored result right on average, and small updates accumulate as they should.
+//!     let mut rng = Rng::new(42);
+//!     axpy![stochastic(rng): w -= lr*g]
+//!
+//! evaluates the statement in `f32` on the widened elements of `w` and `g`, and rounds each result
+//! with fresh bits from `rng` as it is stored.
+
+/// A storage type that statements compute with in a wider type, and that results are rounded
+/// back to at random.
+pub trait Stochastic: Copy {
+    /// The type computed in.
+    type Wide: Copy;
+    /// The stored value, exactly.
+    fn widen(self) -> Self::Wide;
+    /// One of the two values nearest to `w`, the larger with a probability proportional to the
+    /// distance of `w` from the smaller, given uniformly random bits `r`.
+    fn round(w: Self::Wide, r: u32) -> Self;
+}
+
+/// A `bfloat16`, i.e. the upper half of an `f32`, stored as its bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bf16(pub u16);
+
+impl Bf16 {
+    /// `x` rounded to nearest, ties to even.
+    pub fn from_f32(x: f32) -> Bf16 {
+        if x.is_nan() {
+            return Bf16(0x7fc0);
+        }
+        let b = x.to_bits();
+        Bf16((b.wrapping_add(0x7fff + ((b >> 16) & 1)) >> 16) as u16)
+    }
+
+    /// The value as an `f32`, which represents it exactly.
+    #[inline(always)]
+    pub fn to_f32(self) -> f32 {
+        f32::from_bits((self.0 as u32) << 16)
+    }
+}
+
+impl Stochastic for Bf16 {
+    type Wide = f32;
+    #[inline(always)]
+    fn widen(self) -> f32 {
+        self.to_f32()
+    }
+    // adding random bits below the kept ones carries into them with just the right probability
+    #[inline(always)]
+    fn round(w: f32, r: u32) -> Bf16 {
+        if w.is_nan() {
+            return Bf16(0x7fc0);
+        }
+        Bf16((w.to_bits().wrapping_add(r & 0xffff) >> 16) as u16)
+    }
+}
+
+/// A small, fast pseudorandom generator (xorshift64*) for the rounding decisions, not suitable
+/// for anything else that needs good randomness.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// A generator seeded with `seed` (0 is replaced by a fixed nonzero seed).
+    pub fn new(seed: u64) -> Rng {
+        Rng(if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed })
+    }
+
+    /// The next 32 random bits.
+    #[inline(always)]
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32) as u32
+    }
+}
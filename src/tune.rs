@@ -0,0 +1,71 @@
+//! Run-time selection of the tile size for `axpy![tile(auto): ...]`.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Candidate tile sizes, in elements.
+const SIZES: [usize; 4] = [256, 1024, 4096, 16384];
+
+/// Per call site tuner: each of the first `2 * SIZES.len()` runs uses the next candidate and has
+/// its time per element recorded, after which the fastest candidate is used for good. Every run
+/// still does its work exactly once, so statements need not be idempotent.
+pub struct Tuner {
+    next: AtomicUsize,
+    best: AtomicUsize,
+    cost: [AtomicUsize; SIZES.len()],
+}
+
+/// A run in progress, as handed out by `Tuner::start`.
+pub struct Probe {
+    block: usize,
+    trial: Option<(usize, Instant)>,
+}
+
+impl Probe {
+    #[inline(always)]
+    pub fn block(&self) -> usize {
+        self.block
+    }
+}
+
+impl Default for Tuner {
+    fn default() -> Tuner {
+        Tuner::new()
+    }
+}
+
+impl Tuner {
+    pub const fn new() -> Tuner {
+        Tuner {
+            next: AtomicUsize::new(0),
+            best: AtomicUsize::new(0),
+            cost: [const { AtomicUsize::new(usize::MAX) }; SIZES.len()],
+        }
+    }
+
+    /// The tile size for this run, and whether it is timed.
+    pub fn start(&self) -> Probe {
+        let best = self.best.load(Ordering::Relaxed);
+        if best != 0 {
+            return Probe { block: SIZES[best - 1], trial: None };
+        }
+        let k = self.next.fetch_add(1, Ordering::Relaxed);
+        if k < 2 * SIZES.len() {
+            let k = k % SIZES.len();
+            return Probe { block: SIZES[k], trial: Some((k, Instant::now())) };
+        }
+        let k = (0..SIZES.len()).min_by_key(|&k| self.cost[k].load(Ordering::Relaxed)).unwrap_or(0);
+        self.best.store(k + 1, Ordering::Relaxed);
+        Probe { block: SIZES[k], trial: None }
+    }
+
+    /// Records the time taken by a timed run over `n` elements (in nanoseconds per 1024 elements,
+    /// keeping the better of the two runs of each candidate).
+    pub fn finish(&self, probe: Probe, n: usize) {
+        if let Some((k, t)) = probe.trial {
+            let nanos = t.elapsed().as_nanos().saturating_mul(1024) / (n.max(1) as u128);
+            let cost = if nanos > usize::MAX as u128 { usize::MAX } else { nanos as usize };
+            self.cost[k].fetch_min(cost, Ordering::Relaxed);
+        }
+    }
+}
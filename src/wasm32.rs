@@ -0,0 +1,66 @@
+//! simd128 kernels for `axpy![wasm32: ...]`, as WebAssembly runtimes only vectorize what the
+//! module itself spells out in `v128` operations.
+//!
+//! Compiled in when building for wasm32 with the `simd128` target feature (e.g. with
+//! `RUSTFLAGS="-C target-feature=+simd128"`); there is no run-time detection in WebAssembly.
+//!
+//! Turning on `simd128` does not by itself change what plain `axpy!` statements compile to. The
+//! kernels are for `f32` and `f64` only, and the macro sees tokens, not types, so it cannot tell
+//! a float statement from an integer, generic or mixed-coefficient one that must keep the portable
+//! loop: `wasm32:` is the opt-in, for sums of scaled slices assigned by `=`, `+=` or `-=`. Other
+//! shapes, and the reductions, remain whatever the runtime makes of the portable loop.
+
+use core::arch::wasm32::*;
+use core::cmp::min;
+use __rt::combine as scalar;
+
+/// An element type with simd128 kernels, i.e. `f32` or `f64`.
+pub trait Simd: Copy {
+    /// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]`, or `y += ...` if `accumulate`, over the shortest
+    /// length.
+    fn combine<const N: usize>(y: &mut [Self], accumulate: bool, a: [Self; N], x: [&[Self]; N]);
+}
+
+/// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]` (or `y += ...`), see `Simd::combine`.
+#[inline]
+pub fn combine<T: Simd, const N: usize>(y: &mut [T], accumulate: bool, a: [T; N], x: [&[T]; N]) {
+    T::combine(y, accumulate, a, x)
+}
+
+// The vector loop handles whole registers of W elements (with a separate multiply and add, fused
+// multiply-add being part of the relaxed SIMD proposal only), and the remaining elements are done
+// one at a time.
+macro_rules! simd {
+    ($t:ident, $w:expr, $splat:ident, $mul:ident, $add:ident) => {
+        impl Simd for $t {
+            #[inline]
+            fn combine<const N: usize>(y: &mut [$t], accumulate: bool, a: [$t; N], x: [&[$t]; N]) {
+                let n = x.iter().fold(y.len(), |n, x| min(n, x.len()));
+                let m = n - n % $w;
+                let mut av = [$splat(0.); N];
+                for k in 0..N {
+                    av[k] = $splat(a[k]);
+                }
+                let mut i = 0;
+                while i < m {
+                    unsafe {
+                        let mut s = if accumulate {
+                            v128_load(y.as_ptr().add(i) as *const v128)
+                        } else {
+                            $splat(0.)
+                        };
+                        for k in 0..N {
+                            s = $add(s, $mul(av[k], v128_load(x[k].as_ptr().add(i) as *const v128)));
+                        }
+                        v128_store(y.as_mut_ptr().add(i) as *mut v128, s);
+                    }
+                    i += $w;
+                }
+                scalar(&mut y[m..n], accumulate, a, x.map(|x| &x[m..n]));
+            }
+        }
+    };
+}
+
+simd!(f32, 4, f32x4_splat, f32x4_mul, f32x4_add);
+simd!(f64, 2, f64x2_splat, f64x2_mul, f64x2_add);
@@ -0,0 +1,110 @@
+//! Hand-written AVX2 and AVX-512 kernels for `axpy![x86: ...]`, for when the auto-vectorizer
+//! cannot be relied upon.
+//!
+//! The `arch-x86` feature only makes the kernels available: a statement uses them when it is
+//! prefixed with `x86:`, and is a sum of scaled `f32` or `f64` slices assigned by `=`, `+=` or
+//! `-=`. Unprefixed statements, which may be on integers or generic element types the macro cannot
+//! tell apart from floats, keep the generic loop, and so does every reduction.
+
+use core::arch::x86_64::*;
+use core::cmp::min;
+use __rt::combine as scalar;
+#[cfg(feature = "std")]
+use std::is_x86_feature_detected;
+
+/// An element type with x86 kernels, i.e. `f32` or `f64`.
+pub trait Simd: Copy {
+    /// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]`, or `y += ...` if `accumulate`, over the shortest
+    /// length, using the widest instruction set available.
+    fn combine<const N: usize>(y: &mut [Self], accumulate: bool, a: [Self; N], x: [&[Self]; N]);
+}
+
+/// The instruction set used by the kernels: AVX-512 if the CPU has it, or else AVX2 with FMA,
+/// or else none. With the `std` feature this is detected at run time, and otherwise it is what
+/// the crate was compiled for (e.g. with `-C target-cpu=native`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Avx512,
+    Avx2,
+    Scalar,
+}
+
+#[cfg(feature = "std")]
+pub fn level() -> Level {
+    if is_x86_feature_detected!("avx512f") {
+        Level::Avx512
+    } else if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+        Level::Avx2
+    } else {
+        Level::Scalar
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub fn level() -> Level {
+    if cfg!(target_feature = "avx512f") {
+        Level::Avx512
+    } else if cfg!(all(target_feature = "avx2", target_feature = "fma")) {
+        Level::Avx2
+    } else {
+        Level::Scalar
+    }
+}
+
+/// `y = a[0]*x[0] + ... + a[N-1]*x[N-1]` (or `y += ...`), see `Simd::combine`.
+#[inline]
+pub fn combine<T: Simd, const N: usize>(y: &mut [T], accumulate: bool, a: [T; N], x: [&[T]; N]) {
+    T::combine(y, accumulate, a, x)
+}
+
+// The vector loop handles whole registers of W elements, multiplying and adding with FMA, and the
+// remaining elements are done one at a time.
+macro_rules! kernel {
+    ($name:ident, $features:tt, $t:ident, $w:expr, $zero:ident, $set1:ident, $load:ident, $store:ident, $fma:ident) => {
+        #[target_feature(enable = $features)]
+        unsafe fn $name<const N: usize>(y: &mut [$t], accumulate: bool, a: [$t; N], x: [&[$t]; N]) {
+            let n = x.iter().fold(y.len(), |n, x| min(n, x.len()));
+            let m = n - n % $w;
+            let mut av = [$zero(); N];
+            for k in 0..N {
+                av[k] = $set1(a[k]);
+            }
+            let mut i = 0;
+            while i < m {
+                let mut s = if accumulate { $load(y.as_ptr().add(i)) } else { $zero() };
+                for k in 0..N {
+                    s = $fma(av[k], $load(x[k].as_ptr().add(i)), s);
+                }
+                $store(y.as_mut_ptr().add(i), s);
+                i += $w;
+            }
+            scalar(&mut y[m..n], accumulate, a, x.map(|x| &x[m..n]));
+        }
+    };
+}
+
+kernel!(avx2_f32, "avx2,fma", f32, 8, _mm256_setzero_ps, _mm256_set1_ps, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_fmadd_ps);
+kernel!(avx2_f64, "avx2,fma", f64, 4, _mm256_setzero_pd, _mm256_set1_pd, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_fmadd_pd);
+kernel!(avx512_f32, "avx512f", f32, 16, _mm512_setzero_ps, _mm512_set1_ps, _mm512_loadu_ps, _mm512_storeu_ps, _mm512_fmadd_ps);
+kernel!(avx512_f64, "avx512f", f64, 8, _mm512_setzero_pd, _mm512_set1_pd, _mm512_loadu_pd, _mm512_storeu_pd, _mm512_fmadd_pd);
+
+macro_rules! simd {
+    ($t:ident, $avx2:ident, $avx512:ident) => {
+        impl Simd for $t {
+            #[inline]
+            fn combine<const N: usize>(y: &mut [$t], accumulate: bool, a: [$t; N], x: [&[$t]; N]) {
+                match level() {
+                    Level::Avx512 => unsafe { $avx512(y, accumulate, a, x) },
+                    Level::Avx2 => unsafe { $avx2(y, accumulate, a, x) },
+                    Level::Scalar => {
+                        let n = x.iter().fold(y.len(), |n, x| min(n, x.len()));
+                        scalar(&mut y[..n], accumulate, a, x.map(|x| &x[..n]))
+                    }
+                }
+            }
+        }
+    };
+}
+
+simd!(f32, avx2_f32, avx512_f32);
+simd!(f64, avx2_f64, avx512_f64);
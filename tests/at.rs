@@ -0,0 +1,23 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn single_index() {
+    let x: [f64; 4] = [1., 2., 3., 4.];
+    let y = [4., 3., 2., 1.];
+    let z = [0.5, 0.5, 0.5, 0.5];
+    let (a, b) = (2., -1.);
+    let mut w = [0.; 4];
+    axpy![w = a*x + b*y - z];
+    for (i, w) in w.iter().enumerate() {
+        assert_eq!(axpy_at![i; a*x + b*y - z], *w);
+    }
+    assert_eq!(axpy_at![3; max(x - y, z)], 3.);
+    assert_eq!(axpy_at![1; a*sqrt(x) - y], 2. * 2f64.sqrt() - 3.);
+}
+
+#[test]
+#[should_panic]
+fn out_of_bounds() {
+    let x = [1., 2.];
+    axpy_at![2; 2.*x];
+}
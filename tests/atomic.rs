@@ -0,0 +1,37 @@
+#[macro_use] extern crate axpy;
+
+use axpy::atomic::AtomicF64;
+use std::sync::atomic::{AtomicI64, AtomicU64};
+
+#[test]
+fn concurrent_accumulation() {
+    let forces: Vec<AtomicF64> = (0..1000).map(|_| AtomicF64::new(0.)).collect();
+    let impulses: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+    let dt = 0.5;
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let forces = &forces[..];
+                let impulses = &impulses[..];
+                for _ in 0..10 {
+                    axpy![atomic: forces += dt*impulses];
+                }
+            });
+        }
+    });
+    let forces: Vec<f64> = forces.into_iter().map(AtomicF64::into_inner).collect();
+    assert!(forces.iter().zip(impulses.iter()).all(|(f, x)| *f == 20. * x));
+}
+
+#[test]
+fn integer_and_bits() {
+    let counts = [AtomicI64::new(10), AtomicI64::new(20)];
+    let x = [1i64, 2];
+    axpy![atomic: counts -= 3*x];
+    assert_eq!(counts.map(AtomicI64::into_inner), [7, 14]);
+    let bits = [AtomicU64::new(1f64.to_bits()), AtomicU64::new(2f64.to_bits())];
+    let y = AtomicF64::from_bits(&bits);
+    let w = [0.5, 0.25];
+    axpy![atomic: y = 2.*w - y];
+    assert_eq!(bits.map(|b| f64::from_bits(b.into_inner())), [0., -1.5]);
+}
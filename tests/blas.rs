@@ -0,0 +1,26 @@
+extern crate axpy;
+
+use axpy::blas;
+
+type Axpy = fn(f32, &[f32], &mut [f32]);
+
+#[test]
+fn functions() {
+    let x = [1., 2., 3.];
+    let mut y = [1., 1., 1.];
+    blas::daxpy(2., &x, &mut y);
+    assert_eq!(y, [3., 5., 7.]);
+    blas::axpby(1., &x, -1., &mut y);
+    assert_eq!(y, [-2., -3., -4.]);
+    blas::scal(-0.5, &mut y);
+    assert_eq!(y, [1., 1.5, 2.]);
+    assert_eq!(blas::dot(&x, &y), 10.);
+
+    // picked at run time
+    let ops: [Axpy; 2] = [blas::saxpy, blas::axpy];
+    let mut z = [0f32; 2];
+    for op in ops.iter() {
+        op(3., &[1., 2.], &mut z);
+    }
+    assert_eq!(z, [6., 12.]);
+}
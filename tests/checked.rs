@@ -0,0 +1,70 @@
+#[macro_use] extern crate axpy;
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A fixed-point stand-in for `rust_decimal::Decimal`: panicking operators, checked methods.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Fixed(i64);
+
+impl Fixed {
+    fn checked_add(self, o: Fixed) -> Option<Fixed> { self.0.checked_add(o.0).map(Fixed) }
+    fn checked_sub(self, o: Fixed) -> Option<Fixed> { self.0.checked_sub(o.0).map(Fixed) }
+    fn checked_mul(self, o: Fixed) -> Option<Fixed> { self.0.checked_mul(o.0).map(|v| Fixed(v / 100)) }
+}
+
+impl Add for Fixed { type Output = Fixed; fn add(self, o: Fixed) -> Fixed { self.checked_add(o).unwrap() } }
+impl Sub for Fixed { type Output = Fixed; fn sub(self, o: Fixed) -> Fixed { self.checked_sub(o).unwrap() } }
+impl Mul for Fixed { type Output = Fixed; fn mul(self, o: Fixed) -> Fixed { self.checked_mul(o).unwrap() } }
+impl Neg for Fixed { type Output = Fixed; fn neg(self) -> Fixed { Fixed(-self.0) } }
+
+#[test]
+fn decimal() {
+    let a = Fixed(150);
+    let x = [Fixed(200), Fixed(-100)];
+    let mut y = [Fixed(1), Fixed(2)];
+    axpy![y = a*x + y];
+    assert_eq!(y, [Fixed(301), Fixed(-148)]);
+    assert_eq!(axpy![checked_ops: y -= a*x - y], Ok(()));
+    assert_eq!(y, [Fixed(302), Fixed(-146)]);
+}
+
+#[test]
+fn overflow() {
+    let x = [1i32, i32::MAX, 3];
+    let mut y = [1, 2, 3];
+    let r = axpy![checked_ops: y += 2*x - y];
+    assert_eq!(r, Err(axpy::Overflow { index: 1 }));
+    assert_eq!(y, [2, 2, 3]);
+    assert_eq!(axpy![checked_ops: y = -x + 1*y], Ok(()));
+    assert_eq!(y, [1, 2 - i32::MAX, 0]);
+}
+
+#[test]
+fn negated_minimum() {
+    let x = [1i32, i32::MIN];
+    let mut y = [0i32, 0];
+    assert_eq!(axpy![checked_ops: y = -x], Err(axpy::Overflow { index: 1 }));
+    assert_eq!(y, [-1, 0]);
+    let a = 1;
+    assert_eq!(axpy![checked_ops: y = -a*x + y], Err(axpy::Overflow { index: 1 }));
+    assert_eq!(axpy![checked_ops: y = (-a)*x], Err(axpy::Overflow { index: 1 }));
+    assert_eq!(y, [-1, 0]);
+}
+
+#[test]
+fn finite() {
+    let x = [1.0f64, 2.0, 3.0];
+    let mut y = [1.0f64, 1.0, 1.0];
+    axpy![checked_fp: y = 2.*x + y];
+    assert_eq!(y, [3., 5., 7.]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "checked_fp: y[1] became NaN, from y = 1.0, x = inf, w = -inf")]
+fn first_nan() {
+    let x = [1.0f64, f64::INFINITY];
+    let w = [0.0f64, f64::NEG_INFINITY];
+    let mut y = [1.0f64, 1.0];
+    axpy![checked_fp: y += x + w];
+}
@@ -0,0 +1,37 @@
+#[macro_use] extern crate axpy;
+
+use axpy::complex::Complex;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct C64 { re: f64, im: f64 }
+
+unsafe impl Complex for C64 {
+    type Real = f64;
+    fn parts(self) -> [f64; 2] { [self.re, self.im] }
+}
+
+#[test]
+fn complex() {
+    let x = [C64 { re: 1., im: 2. }, C64 { re: -1., im: 0. }];
+    let y = [C64 { re: 0., im: 1. }, C64 { re: 3., im: -2. }];
+    let mut z = [C64 { re: 1., im: 1. }; 2];
+    let a = C64 { re: 0., im: 1. };
+    // z = i*x - 2*y + z
+    axpy![complex: z = a*x - 2.*y + z];
+    assert_eq!(z, [C64 { re: -1., im: -0. }, C64 { re: -5., im: 4. }]);
+    let b = [2., 0.5];
+    let mut w = [[1., 0.]; 2];
+    axpy![complex: w -= b*w];
+    assert_eq!(w, [[-1., -0.5]; 2]);
+}
+
+#[test]
+fn conjugated_dot() {
+    let x = [C64 { re: 1., im: 2. }, C64 { re: 0., im: -1. }];
+    let y = [C64 { re: 3., im: -1. }, C64 { re: 2., im: 2. }];
+    // (1-2i)(3-i) + (i)(2+2i) = (1-7i) + (-2+2i)
+    assert_eq!(dotc![x, y], [-1., -5.]);
+    let [re, im] = dotc![x, x];
+    assert_eq!((re, im), (6., 0.));
+}
@@ -0,0 +1,34 @@
+#[macro_use] extern crate axpy;
+
+use std::ops::{Add, AddAssign, Mul};
+
+/// A stand-in for `glam::Vec3`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Vec3 { x: f32, y: f32, z: f32 }
+
+unsafe impl axpy::Components<f32> for Vec3 {}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+    fn add(self, o: Vec3) -> Vec3 { Vec3 { x: self.x + o.x, y: self.y + o.y, z: self.z + o.z } }
+}
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, o: Vec3) { *self = *self + o }
+}
+impl Mul<Vec3> for f32 {
+    type Output = Vec3;
+    fn mul(self, v: Vec3) -> Vec3 { Vec3 { x: self * v.x, y: self * v.y, z: self * v.z } }
+}
+
+#[test]
+fn particles() {
+    let v = Vec3 { x: 1., y: -2., z: 4. };
+    let velocities = [v; 5];
+    let mut positions = [Vec3 { x: 0., y: 0., z: 0. }; 5];
+    let dt = 0.5f32;
+    axpy![positions += dt * velocities];
+    assert_eq!(positions[4], Vec3 { x: 0.5, y: -1., z: 2. });
+    axpy![components(f32): positions += dt * velocities + velocities];
+    assert_eq!(positions, [Vec3 { x: 2., y: -4., z: 8. }; 5]);
+}
@@ -0,0 +1,19 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn smoothing() {
+    let x = [0., 4., 8., 4., 0., 0.];
+    let mut y = [-1.; 6];
+    conv![y = [0.25, 0.5, 0.25] * x];
+    assert_eq!(y, [4., 6., 4., 1., -1., -1.]);
+}
+
+#[test]
+fn fir_accumulate() {
+    // an asymmetric kernel, to check that it is flipped
+    let k: [i32; 4] = [1, 2, 3, 4];
+    let x: Vec<i32> = (1..=6).collect();
+    let mut y = vec![100; 2];
+    conv![y += k * x];
+    assert_eq!(y, [100 + 4 + 2 * 3 + 3 * 2 + 4, 100 + 5 + 2 * 4 + 3 * 3 + 4 * 2]);
+}
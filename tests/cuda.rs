@@ -0,0 +1,99 @@
+#![cfg(feature = "cuda")]
+#[macro_use] extern crate axpy;
+
+use axpy::cuda::{Cublas, Dev, Handle, Status};
+use std::ffi::c_int;
+use std::ops::Neg;
+
+// there is no GPU to run on, so this only checks the statements (being generic, it is not
+// instantiated, and needs no cuBLAS to link against)
+#[allow(dead_code)]
+fn statements<T>(h: Handle, y: &mut Dev<T>, x: &Dev<T>, w: &Dev<T>, a: T) -> Result<(), Status>
+    where T: Cublas + From<u8> + From<i8> + Neg<Output = T>
+{
+    axpy![cuda(h): y = a*x - w]?;
+    axpy![cuda(h): y += x]?;
+    axpy![cuda(h): y -= a*w]
+}
+
+/// An element whose "device" is host memory, for running the statements without a GPU.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Host(f64);
+
+impl From<u8> for Host {
+    fn from(v: u8) -> Host { Host(v.into()) }
+}
+
+impl From<i8> for Host {
+    fn from(v: i8) -> Host { Host(v.into()) }
+}
+
+impl Neg for Host {
+    type Output = Host;
+    fn neg(self) -> Host { Host(-self.0) }
+}
+
+unsafe fn slice<'a>(p: *const Host, n: c_int) -> &'a [Host] {
+    std::slice::from_raw_parts(p, n as usize)
+}
+
+unsafe fn slice_mut<'a>(p: *mut Host, n: c_int) -> &'a mut [Host] {
+    std::slice::from_raw_parts_mut(p, n as usize)
+}
+
+impl Cublas for Host {
+    unsafe fn axpy(_: Handle, n: c_int, a: Host, x: *const Host, y: *mut Host) -> c_int {
+        for (y, x) in slice_mut(y, n).iter_mut().zip(slice(x, n)) {
+            y.0 += a.0 * x.0;
+        }
+        0
+    }
+    unsafe fn scal(_: Handle, n: c_int, a: Host, x: *mut Host) -> c_int {
+        for x in slice_mut(x, n) {
+            x.0 *= a.0;
+        }
+        0
+    }
+    unsafe fn copy(_: Handle, n: c_int, x: *const Host, y: *mut Host) -> c_int {
+        slice_mut(y, n).copy_from_slice(slice(x, n));
+        0
+    }
+    unsafe fn geam(_: Handle, n: c_int, a: Host, x: *const Host, b: Host, w: *const Host, y: *mut Host) -> c_int {
+        for (y, (x, w)) in slice_mut(y, n).iter_mut().zip(slice(x, n).iter().zip(slice(w, n))) {
+            y.0 = a.0 * x.0 + b.0 * w.0;
+        }
+        0
+    }
+}
+
+fn host(v: &[f64]) -> Vec<Host> {
+    v.iter().map(|&v| Host(v)).collect()
+}
+
+#[test]
+fn statements_on_host_memory() {
+    let h = Handle(std::ptr::null_mut());
+    let (mut ys, mut xs, mut ws, mut vs) =
+        (host(&[9., 9., 9.]), host(&[1., 2., 3.]), host(&[1., 1., 1.]), host(&[0., 1., 0.]));
+    let mut y = unsafe { Dev::from_raw(ys.as_mut_ptr(), 3) };
+    let x = unsafe { Dev::from_raw(xs.as_mut_ptr(), 3) };
+    let w = unsafe { Dev::from_raw(ws.as_mut_ptr(), 3) };
+    let v = unsafe { Dev::from_raw(vs.as_mut_ptr(), 3) };
+    let a = Host(2.);
+    assert_eq!(axpy![cuda(h): y = a*x - w], Ok(()));
+    assert_eq!(ys, host(&[1., 3., 5.]));
+    assert_eq!(axpy![cuda(h): y = x], Ok(()));
+    assert_eq!(ys, host(&[1., 2., 3.]));
+    assert_eq!(axpy![cuda(h): y = x + w - a*v], Ok(()));
+    assert_eq!(ys, host(&[2., 1., 4.]));
+    assert_eq!(axpy![cuda(h): y += a*x + v], Ok(()));
+    assert_eq!(ys, host(&[4., 6., 10.]));
+    assert_eq!(axpy![cuda(h): y -= x - a*w], Ok(()));
+    assert_eq!(ys, host(&[5., 6., 9.]));
+}
+
+#[test]
+fn empty() {
+    let y = unsafe { Dev::<f64>::from_raw(std::ptr::null_mut(), 0) };
+    assert!(y.is_empty());
+}
@@ -0,0 +1,46 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn expand() {
+    let x: [f64; 3] = [1., 2., 3.];
+    let y: [f64; 3] = [4., 5., 6.];
+    let mut z: [f64; 3] = [1., 1., 1.];
+    let code = axpy_expand![z += 2.*x - y + z];
+    assert_eq!(z, [0., 1., 2.]);
+    let code: String = code.split_whitespace().collect();
+    assert_eq!(code, "for(car,(s0,s1))inz.iter_mut().zip(x.iter().zip(y.iter())){*car+=$crate::__rt::Scale($crate::__rt::Term(2.,(*s0))).get()-(*s1)+(*car);}");
+}
+
+#[test]
+fn repeated_operand() {
+    let x: [f64; 3] = [1., 2., 3.];
+    let y: [f64; 3] = [4., 5., 6.];
+    let mut z: [f64; 3] = [0., 0., 0.];
+    let code = axpy_expand![z = x + 2.*x + y - x];
+    assert_eq!(z, [6., 9., 12.]);
+    let code: String = code.split_whitespace().collect();
+    assert_eq!(code, "for(car,(s0,s1))inz.iter_mut().zip(x.iter().zip(y.iter())){*car=(*s0)+$crate::__rt::Scale($crate::__rt::Term(2.,(*s0))).get()+(*s1)-(*s0);}");
+}
+
+#[test]
+fn folded_signs() {
+    let x: [u32; 3] = [1, 2, 3];
+    let y: [u32; 3] = [9, 9, 9];
+    let mut z: [u32; 3] = [0; 3];
+    // no negated terms, so unsigned elements are fine
+    let code = axpy_expand![z = y - 2*x - x];
+    assert_eq!(z, [6, 3, 0]);
+    let code: String = code.split_whitespace().collect();
+    assert_eq!(code, "for(car,(s0,s1))inz.iter_mut().zip(y.iter().zip(x.iter())){*car=(*s0)-$crate::__rt::Scale($crate::__rt::Term(2,(*s1))).get()-(*s1);}");
+}
+
+#[test]
+fn flat_sources() {
+    let (a, b, c, d, e, f) = ([1i32; 2], [2i32; 2], [3i32; 2], [4i32; 2], [5i32; 2], [6i32; 2]);
+    let mut z = [0i32; 2];
+    let code = axpy_expand![z = a + b + c + d + e + 2*f - a];
+    assert_eq!(z, [26; 2]);
+    let code: String = code.split_whitespace().collect();
+    assert!(code.starts_with("for(car,((s0,s1),((s2,s3),(s4,s5))))in"));
+    assert!(code.ends_with("{*car=(*s0)+(*s1)+(*s2)+(*s3)+(*s4)+$crate::__rt::Scale($crate::__rt::Term(2,(*s5))).get()-(*s0);}"));
+}
@@ -0,0 +1,131 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn unary_functions() {
+    let x: [f64; 4] = [1., 4., 9., 16.];
+    let y: [f64; 4] = [-1., 2., -3., 4.];
+    let mut z: [f64; 4] = [0.5, 0.25, 2., 4.];
+    axpy![z = 2.*sqrt(x) - abs(y) + recip(z)];
+    assert_eq!(z, [3., 6., 3.5, 4.25]);
+    let w: [i32; 3] = [-1, 0, 5];
+    let mut v: [i32; 3] = [1, 1, 1];
+    axpy![v -= abs(w) + 3*signum(w)];
+    assert_eq!(v, [3, 1, -7]);
+}
+
+#[test]
+fn clamp_min_max() {
+    let x: [f64; 4] = [1., 2., 3., 4.];
+    let y: [f64; 4] = [4., 3., 2., 1.];
+    let mut z: [f64; 4] = [0.; 4];
+    axpy![z = clamp(x - 2.*y, -2., 1.)];
+    assert_eq!(z, [-2., -2., -1., 1.]);
+    axpy![z = max(x, y)];
+    assert_eq!(z, [4., 3., 3., 4.]);
+    axpy![z = min(x, 2.*y - z, z)];
+    assert_eq!(z, [1., 2., 1., -2.]);
+    axpy![z += max(x - y, -x)];
+    assert_eq!(z, [0., 1., 2., 1.]);
+}
+
+#[test]
+fn iterator_operands() {
+    let x: [f64; 4] = [1., 2., 3., 4.];
+    let mut z: [f64; 4] = [0.; 4];
+    let f = (0..).map(|i| i as f64);
+    axpy![z = x + 2.*iter(f)];
+    assert_eq!(z, [1., 4., 7., 10.]);
+    let g = x.iter().map(|x| -x);
+    axpy![z = abs(iter(g)) - iter(g) + z];
+    assert_eq!(z, [3., 8., 13., 18.]);
+}
+
+#[test]
+fn loop_index() {
+    let x: [f64; 4] = [1., 2., 3., 4.];
+    let mut z: [f64; 4] = [0.; 4];
+    axpy![z[i] = x + (i as f64)*x];
+    assert_eq!(z, [1., 4., 9., 16.]);
+    axpy![z[k] = (k as f64)*z];
+    assert_eq!(z, [0., 4., 18., 48.]);
+}
+
+#[test]
+fn cast_operands() {
+    let x = [1.5f64, -2.25];
+    let y = [1f32, 2.];
+    let k = [3u8, 4];
+    let mut z = [0f32; 2];
+    axpy![z = x as f32 + 2.*y - k as f32];
+    assert_eq!(z, [0.5, -2.25]);
+    axpy![z -= 2.*x as f32];
+    assert_eq!(z, [-2.5, 2.25]);
+}
+
+#[test]
+fn elementwise_assign() {
+    let w = [0.5, 2., 4.];
+    let s = [2., 4., 8.];
+    let mut z = [2., 3., 4.];
+    axpy![z .*= w];
+    assert_eq!(z, [1., 6., 16.]);
+    axpy![z ./= s];
+    assert_eq!(z, [0.5, 1.5, 2.]);
+    axpy![strict: z .*= 2.*w + s];
+    assert_eq!(z, [1.5, 12., 32.]);
+}
+
+#[test]
+fn returns_target() {
+    fn norm1(v: &mut [f64]) -> f64 {
+        v.iter().map(|x| x.abs()).sum()
+    }
+    let b = [1., 2., 3.];
+    let q = [2., 2., 2.];
+    let mut r = vec![0.; 3];
+    assert_eq!(norm1(axpy![&mut r = b - q]), 2.);
+    axpy![&mut r += q][0] = 5.;
+    assert_eq!(r, [5., 2., 3.]);
+}
+
+#[test]
+fn postfix_coefficients() {
+    let x: [f64; 2] = [1., 2.];
+    let y: [f64; 2] = [3., 4.];
+    let b = 0.5;
+    let mut z = [0.; 2];
+    axpy![z = x*2.0 + y*(b)];
+    assert_eq!(z, [3.5, 6.]);
+    axpy![z -= -x*3. - 2.*y*(-b)];
+    assert_eq!(z, [3.5, 8.]);
+}
+
+#[test]
+fn division() {
+    let x: [f64; 2] = [1., 2.];
+    let y: [f64; 2] = [3., 6.];
+    let h = 0.5;
+    let mut z = [0.; 2];
+    axpy![z = (y - x) / h];
+    assert_eq!(z, [4., 8.]);
+    axpy![z -= x / h - (2.*x - y) / h];
+    assert_eq!(z, [0., 0.]);
+    let n: [i32; 2] = [7, -9];
+    let mut m = [0; 2];
+    axpy![m = n / 2 + 3*n / 4];
+    assert_eq!(m, [3 + 5, -4 - 6]);
+}
+
+#[test]
+fn macro_operands() {
+    macro_rules! view {
+        ($buf:ident, $k:expr) => { &$buf[$k..$k + 2] };
+    }
+    let buf: [f64; 5] = [1., 2., 3., 4., 5.];
+    let x: [f64; 2] = [10., 20.];
+    let mut z = [0.; 2];
+    axpy![z = x + view!(buf, 3)];
+    assert_eq!(z, [14., 25.]);
+    axpy![z -= 2.*view![buf, 0] - z / 2.];
+    assert_eq!(z, [19., 33.5]);
+}
@@ -0,0 +1,57 @@
+#[macro_use] extern crate axpy;
+
+use std::ops::{Index, IndexMut};
+
+// stand-ins for `faer::ColRef` and `faer::ColMut`, with the methods that `faer:` uses: a column
+// of `n` elements `stride` apart
+#[derive(Clone, Copy)]
+struct ColRef<'a> { data: &'a [f64], n: usize, stride: usize }
+struct ColMut<'a> { data: &'a mut [f64], n: usize, stride: usize }
+
+impl<'a> ColRef<'a> {
+    fn rb(&self) -> ColRef<'_> { *self }
+    fn nrows(&self) -> usize { self.n }
+    fn try_as_slice(self) -> Option<&'a [f64]> {
+        if self.stride == 1 { Some(&self.data[..self.n]) } else { None }
+    }
+}
+
+impl<'a> Index<usize> for ColRef<'a> {
+    type Output = f64;
+    fn index(&self, i: usize) -> &f64 { &self.data[i * self.stride] }
+}
+
+impl<'a> ColMut<'a> {
+    fn rb(&self) -> ColRef<'_> { ColRef { data: self.data, n: self.n, stride: self.stride } }
+    fn rb_mut(&mut self) -> ColMut<'_> { ColMut { data: self.data, n: self.n, stride: self.stride } }
+    fn nrows(&self) -> usize { self.n }
+    fn try_as_slice_mut(self) -> Option<&'a mut [f64]> {
+        if self.stride == 1 { Some(&mut self.data[..self.n]) } else { None }
+    }
+}
+
+impl<'a> Index<usize> for ColMut<'a> {
+    type Output = f64;
+    fn index(&self, i: usize) -> &f64 { &self.data[i * self.stride] }
+}
+
+impl<'a> IndexMut<usize> for ColMut<'a> {
+    fn index_mut(&mut self, i: usize) -> &mut f64 { &mut self.data[i * self.stride] }
+}
+
+#[test]
+fn columns() {
+    // a 3x2 column-major matrix, whose columns have unit stride, and its rows, which do not
+    let m = [1., 2., 3., 10., 20., 30.];
+    let (x, w) = (ColRef { data: &m, n: 3, stride: 1 }, ColRef { data: &m[3..], n: 3, stride: 1 });
+    let mut buf = [1.; 3];
+    let mut y = ColMut { data: &mut buf, n: 3, stride: 1 };
+    axpy![faer: y = 2.*x + w - y];
+    assert_eq!(buf, [11., 23., 35.]);
+
+    let r = ColRef { data: &m, n: 2, stride: 3 };
+    let mut buf = [0.; 4];
+    let mut y = ColMut { data: &mut buf, n: 2, stride: 2 };
+    axpy![faer: y += r];
+    assert_eq!(buf, [1., 0., 10., 0.]);
+}
@@ -0,0 +1,14 @@
+#[macro_use] extern crate axpy;
+
+const X: [f64; 4] = [1., 2., 3., 4.];
+const Y: [f64; 4] = [0.5, 0.5, 0.5, 0.5];
+const A: f64 = -1.;
+
+const Z: [f64; 4] = axpy_const![2.*X + Y];
+static W: [f64; 4] = axpy_const![-Y + A*X - X];
+
+#[test]
+fn const_arrays() {
+    assert_eq!(Z, [2.5, 4.5, 6.5, 8.5]);
+    assert_eq!(W, [-2.5, -4.5, -6.5, -8.5]);
+}
@@ -0,0 +1,65 @@
+#[macro_use] extern crate axpy;
+
+use axpy::LengthMismatch;
+
+#[test]
+fn checked() {
+    let x: [f64; 3] = [1., 2., 3.];
+    let w: [f64; 2] = [1., 2.];
+    let mut z: [f64; 3] = [0.; 3];
+    assert_eq!(axpy![checked: z = 2.*x + z], Ok(()));
+    assert_eq!(z, [2., 4., 6.]);
+    let e = axpy![checked: z = x + w];
+    assert_eq!(e, Err(LengthMismatch { operand: "w", len: 2, target: "z", expected: 3 }));
+    assert_eq!(z, [2., 4., 6.]);
+    axpy![truncate: z = x - w];
+    assert_eq!(z, [0., 0., 6.]);
+}
+
+#[test]
+#[should_panic(expected = "`w` has 2 elements, but the target `z` has 3")]
+fn strict() {
+    let x: [f64; 3] = [1., 2., 3.];
+    let w: [f64; 2] = [1., 2.];
+    let mut z: [f64; 3] = [0.; 3];
+    axpy![strict: z = x - w];
+}
+
+#[test]
+fn explicit() {
+    let x: [f64; 4] = [1., 2., 3., 4.];
+    let w: &[f64] = &[1.; 3];
+    let mut z: [f64; 5] = [0.; 5];
+    axpy![3; z = 2.*x + w];
+    assert_eq!(z, [3., 5., 7., 0., 0.]);
+    axpy![(w.len() - 1); z[i] = x + (i as f64)*w];
+    assert_eq!(z, [1., 3., 7., 0., 0.]);
+}
+
+#[test]
+#[should_panic]
+fn explicit_too_long() {
+    let x: [f64; 3] = [1., 2., 3.];
+    let mut z: [f64; 4] = [0.; 4];
+    axpy![4; z = x];
+}
+
+#[test]
+fn exact() {
+    let x = [1f64, 2., 3.].to_vec();
+    let w: [f64; 3] = [1., 1., 1.];
+    let mut z: Box<[f64]> = vec![0.; 3].into_boxed_slice();
+    axpy![exact: z[i] = x - (i as f64)*w];
+    assert_eq!(*z, [1., 1., 1.]);
+    axpy![exact: z = z + x];
+    assert_eq!(*z, [2., 3., 4.]);
+}
+
+#[test]
+#[should_panic(expected = "`w` has 2 elements, but the target `z` has 3")]
+fn exact_mismatch() {
+    let x: [f64; 3] = [1., 2., 3.];
+    let w: [f64; 2] = [1., 2.];
+    let mut z: [f64; 3] = [0.; 3];
+    axpy![exact: z = x - w];
+}
@@ -0,0 +1,214 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn rot_givens() {
+    let mut x: [f64; 3] = [1., 0., 2.];
+    let mut y: [f64; 3] = [0., 1., 3.];
+    let (c, s) = (0., 1.);
+    rot![x, y; c, s];
+    assert_eq!(x, [0., 1., 3.]);
+    assert_eq!(y, [-1., 0., -2.]);
+}
+
+#[test]
+fn rot_general() {
+    let mut x: [i32; 3] = [1, 2, 3];
+    let mut y: [i32; 3] = [4, 5, 6];
+    rot![(x, y) = (x + y, x - 2*y)];
+    assert_eq!(x, [5, 7, 9]);
+    assert_eq!(y, [-7, -8, -9]);
+}
+
+#[test]
+fn swap_vectors() {
+    let mut x: [i32; 3] = [1, 2, 3];
+    let mut y: [i32; 3] = [4, 5, 6];
+    swap![x, y];
+    assert_eq!((x, y), ([4, 5, 6], [1, 2, 3]));
+    let (px, py) = (x.as_mut_ptr(), y.as_mut_ptr());
+    unsafe { swap![ptr(2): px, py]; }
+    assert_eq!((x, y), ([1, 2, 6], [4, 5, 3]));
+}
+
+#[test]
+fn copy_ranges() {
+    let x: [i32; 6] = [1, 2, 3, 4, 5, 6];
+    let mut y: [i32; 3] = [0; 3];
+    copy![y = x];
+    assert_eq!(y, [1, 2, 3]);
+    copy![y = x[3..]];
+    assert_eq!(y, [4, 5, 6]);
+    copy![y = x[1..; 2]];
+    assert_eq!(y, [2, 4, 6]);
+}
+
+#[test]
+fn scal_in_place() {
+    let mut x: [f64; 3] = [1., 2., 4.];
+    scal![x *= 2.];
+    assert_eq!(x, [2., 4., 8.]);
+    scal![x /= 4.];
+    assert_eq!(x, [0.5, 1., 2.]);
+    axpy![x = 2. * x];
+    assert_eq!(x, [1., 2., 4.]);
+}
+
+// A vector that records how many passes are made over it.
+struct Counted { data: [i32; 4], reads: std::cell::Cell<usize>, writes: usize }
+
+impl Counted {
+    fn new(data: [i32; 4]) -> Counted { Counted { data, reads: std::cell::Cell::new(0), writes: 0 } }
+    fn iter(&self) -> std::slice::Iter<'_, i32> { self.reads.set(self.reads.get() + 1); self.data.iter() }
+    fn iter_mut(&mut self) -> std::slice::IterMut<'_, i32> { self.writes += 1; self.data.iter_mut() }
+    #[allow(dead_code)] // with the `strict` feature
+    fn len(&self) -> usize { self.data.len() }
+}
+
+#[test]
+fn axpby_single_pass() {
+    let (a, b) = (2, 3);
+    let mut x = Counted::new([1, 2, 3, 4]);
+    let mut y = Counted::new([1, 1, 1, 1]);
+    axpy![y = b*y + a*x];
+    assert_eq!(y.data, [5, 7, 9, 11]);
+    assert_eq!((y.reads.get(), y.writes), (0, 1));
+    assert_eq!((x.reads.get(), x.writes), (1, 0));
+    axpy![x = b*x];
+    assert_eq!(x.data, [3, 6, 9, 12]);
+    assert_eq!((x.reads.get(), x.writes), (1, 1));
+}
+
+#[test]
+fn lerp_endpoints() {
+    let x: [f64; 3] = [0., 1., 2.];
+    let y: [f64; 3] = [4., 5., 10.];
+    let mut z: [f64; 3] = [0.; 3];
+    lerp![z = x, y; 0.25];
+    assert_eq!(z, [1., 2., 4.]);
+    lerp![z = z, y; 0.5];
+    assert_eq!(z, [2.5, 3.5, 7.]);
+    lerp![z = x, z; 0.];
+    assert_eq!(z, x);
+}
+
+#[test]
+fn many_terms() {
+    // 32 distinct operands (and the target) at the default recursion limit
+    let (v0, v1, v2, v3, v4, v5, v6, v7) = ([0.; 2], [1.; 2], [2.; 2], [3.; 2], [4.; 2], [5.; 2], [6.; 2], [7.; 2]);
+    let (v8, v9, v10, v11, v12, v13, v14, v15) = ([8.; 2], [9.; 2], [10.; 2], [11.; 2], [12.; 2], [13.; 2], [14.; 2], [15.; 2]);
+    let (v16, v17, v18, v19, v20, v21, v22, v23) = ([16.; 2], [17.; 2], [18.; 2], [19.; 2], [20.; 2], [21.; 2], [22.; 2], [23.; 2]);
+    let (v24, v25, v26, v27, v28, v29, v30, v31) = ([24.; 2], [25.; 2], [26.; 2], [27.; 2], [28.; 2], [29.; 2], [30.; 2], [31.; 2]);
+    let mut z = [1.; 2];
+    axpy![z = v0 + 2.*v1 - 3.*v2 + 1.*v3 + v4 + 3.*v5 + 1.*v6 - 2.*v7 + v8 + 1.*v9 + 2.*v10 + 3.*v11 - v12 + 2.*v13 + 3.*v14 + 1.*v15 + v16 - 3.*v17 + 1.*v18 + 2.*v19 + v20 + 1.*v21 - 2.*v22 + 3.*v23 + v24 + 2.*v25 + 3.*v26 - 1.*v27 + v28 + 3.*v29 + 1.*v30 + 2.*v31 - 2.*z + v0];
+    assert_eq!(z, [568.0; 2]);
+}
+
+#[test]
+fn reproducible() {
+    let (a, b) = (0.1f64, 0.2f64);
+    let x = [3.0f64; 5];
+    let y = [-3.0f64; 5];
+    let mut z = [1.0f64; 5];
+    axpy![reproducible: x86: z += a*x - b*y];
+    for &v in &z {
+        assert_eq!(v.to_bits(), (1.0 + ((0.1f64 * 3.0) - (0.2f64 * -3.0))).to_bits());
+    }
+}
+
+#[test]
+fn reproducible_without_fma() {
+    // a*x is 1 - 2^-60, which rounds to 1, so that only a fused multiply-add leaves anything of
+    // -w + a*x (as the x86 kernels do, on whole registers)
+    let a = 1. + 2f64.powi(-30);
+    let x = [1. - 2f64.powi(-30); 19];
+    let w = [1.0f64; 19];
+    assert_ne!(a.mul_add(x[0], -w[0]), 0.);
+    let mut z = [f64::NAN; 19];
+    axpy![reproducible: x86: z = -w + a*x];
+    assert_eq!(z, [0.; 19]);
+    let mut z = [f64::NAN; 19];
+    axpy![reproducible: tail(masked, 4): z = -w + a*x];
+    assert_eq!(z, [0.; 19]);
+}
+
+#[test]
+fn sparse() {
+    let idx: [u32; 3] = [4, 0, 2];
+    let vals = [1.0f64, 2.0, 3.0];
+    let mut y = [1.0f64; 5];
+    let a = 2.;
+    axpy![y += a*sparse(idx, vals)];
+    assert_eq!(y, [5., 1., 7., 1., 3.]);
+    let idx: Vec<usize> = vec![1, 3];
+    axpy![y -= sparse(idx, vals)];
+    assert_eq!(y, [5., 0., 7., -1., 3.]);
+}
+
+#[test]
+fn stencils() {
+    let x = [1., 4., 9., 16., 25.];
+    let mut z = [0.; 5];
+    axpy![stencil(skip): z = x(-1) - 2.0*x + x(+1)];
+    assert_eq!(z, [0., 2., 2., 2., 0.]);
+    axpy![stencil(clamp): z = x(-1) - 2.0*x + x(1)];
+    assert_eq!(z, [3., 2., 2., 2., -9.]);
+    let c = 1.;
+    axpy![stencil(wrap): z = c*x(1) - c*x(-1)];
+    assert_eq!(z, [-21., 8., 12., 16., -15.]);
+}
+
+#[test]
+fn rotations() {
+    let x = [1., 4., 9., 16., 25.];
+    let y = [1., 1., 1., 1., 1.];
+    let mut z = [0.; 5];
+    axpy![z = rot(x, 1) - rot(x, -1)];
+    assert_eq!(z, [-21., 8., 12., 16., -15.]);
+    let head = 7;
+    axpy![z = y + 2.*rot(x, head)];
+    assert_eq!(z, [19., 33., 51., 3., 9.]);
+    axpy![z += rot(y, 0)];
+    assert_eq!(z, [20., 34., 52., 4., 10.]);
+}
+
+#[test]
+fn reversed() {
+    let x = [1., 2., 3., 4.];
+    let y = [10., 20., 30., 40., 50.];
+    let mut z = [0.; 4];
+    axpy![z = x + 2.*rev(y)];
+    assert_eq!(z, [101., 82., 63., 44.]);
+    axpy![z = x - rev(x)];
+    assert_eq!(z, [-3., -1., 1., 3.]);
+    assert_eq!(dot![x, rev(x)], 20.);
+}
+
+#[test]
+fn sinks() {
+    let x = [1., 2., 3., 4.];
+    let y = [1., 1., 1., 1.];
+    let a = 2.;
+    let mut out = vec![0.];
+    axpy![extend(out): a*x + y];
+    assert_eq!(out, [0., 3., 5., 7., 9.]);
+    // every other value
+    let mut every_other = Vec::new();
+    axpy![each(|i, v| if i % 2 == 0 { every_other.push(v) }): x - y];
+    assert_eq!(every_other, [0., 2.]);
+}
+
+#[test]
+fn masks() {
+    let x = [1., 5., 3., -2.];
+    let y = [1., 2., 4., -4.];
+    let mut m = [false; 4];
+    let tol = 1.5;
+    axpy![m = (x - y) > tol];
+    assert_eq!(m, [false, true, false, true]);
+    axpy![m = x <= 2.0*y];
+    assert_eq!(m, [true, false, true, false]);
+    axpy![m = (2.*x + y) != -3.];
+    assert_eq!(m, [true, true, true, true]);
+    axpy![m = x == 3.];
+    assert_eq!(m, [false, false, true, false]);
+}
@@ -0,0 +1,143 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn gemv_full() {
+    let a: [f64; 6] = [1., 2., 3.,
+                       4., 5., 6.];
+    let x: [f64; 3] = [1., 0., -1.];
+    let mut y: [f64; 2] = [10., 20.];
+    gemv![y = 2. * panel(a, 2, 3, 3) * x + 0.5 * y];
+    assert_eq!(y, [1., 6.]);
+}
+
+#[test]
+fn gemv_accumulate() {
+    let a: [i32; 6] = [1, 2,
+                       3, 4,
+                       5, 6];
+    let x: [i32; 2] = [1, 1];
+    let mut y: [i32; 3] = [1, 1, 1];
+    gemv![y += panel(a, 3, 2, 2) * x];
+    assert_eq!(y, [4, 8, 12]);
+    gemv![y = panel(a, 3, 2, 2) * x + y];
+    assert_eq!(y, [7, 15, 23]);
+}
+
+#[test]
+fn gemv_strided() {
+    // the leading 2x3 block of a 3x4 buffer, ignoring the padding column and the last row
+    let a: [f64; 12] = [1., 2., 3., 99.,
+                        4., 5., 6., 99.,
+                        99., 99., 99., 99.];
+    let x: [f64; 4] = [1., 1., 1., 99.];
+    let mut y: [f64; 3] = [0., 0., 7.];
+    gemv![y = panel(a, 2, 3, 4) * x];
+    assert_eq!(y, [6., 15., 7.]);
+}
+
+#[test]
+#[should_panic(expected = "gemv!: a matrix of 3 rows of 3 elements, 4 apart, does not fit in 8 elements")]
+fn gemv_matrix_too_short() {
+    let a: [f64; 8] = [1., 2., 3., 0.,
+                       4., 5., 6., 0.];
+    let x: [f64; 3] = [1., 1., 1.];
+    let mut y: [f64; 3] = [0.; 3];
+    gemv![y = panel(a, 3, 3, 4) * x];
+}
+
+#[test]
+#[should_panic(expected = "gemv!: expected vectors of at least 3 and 2 elements, found 2 and 2")]
+fn gemv_vector_too_short() {
+    let a: [f64; 6] = [1., 2., 3.,
+                       4., 5., 6.];
+    let x: [f64; 2] = [1., 1.];
+    let mut y: [f64; 2] = [0.; 2];
+    gemv![y = panel(a, 2, 3, 3) * x];
+}
+
+#[test]
+fn ger_rank_one() {
+    let x: [f64; 2] = [1., 2.];
+    let y: [f64; 3] = [1., 0., -1.];
+    let mut a: [f64; 6] = [0., 1., 2.,
+                           3., 4., 5.];
+    ger![a += 2. * x * y^T];
+    assert_eq!(a, [2., 1., 0.,
+                   7., 4., 1.]);
+    ger![a -= x * y^T];
+    assert_eq!(a, [1., 1., 1.,
+                   5., 4., 3.]);
+}
+
+#[test]
+fn columns() {
+    // 3x2 row-major
+    let mut a: [f64; 6] = [1., 2., 3., 4., 5., 6.];
+    let x: [f64; 3] = [1., 1., 1.];
+    axpy![col(a, 1, 2) += 2.*x];
+    assert_eq!(a, [1., 4., 3., 6., 5., 8.]);
+    let b: [f64; 6] = a;
+    let mut y: [f64; 3] = [0.; 3];
+    axpy![y = col(b, 0, 2) - col(b, 1, 2) + y];
+    assert_eq!(y, [-3., -3., -3.]);
+}
+
+#[test]
+fn interleaved_channels() {
+    // stereo frames L R L R L R
+    let inp: [f32; 6] = [1., -1., 2., -2., 3., -3.];
+    let dry: [f32; 3] = [0.5; 3];
+    let mut out = [0f32; 6];
+    let g = 2.;
+    axpy![channel(out, 0, 2) = g*channel(inp, 0, 2) + dry];
+    axpy![channel(out, 1, 2) = channel(inp, 1, 2) - dry];
+    assert_eq!(out, [2.5, -1.5, 4.5, -2.5, 6.5, -3.5]);
+}
+
+#[test]
+fn spmv_csr() {
+    // [[1, 0, 2], [0, 0, 0], [0, 3, 4]]
+    let indptr: [u32; 4] = [0, 2, 2, 4];
+    let indices: [u32; 4] = [0, 2, 1, 2];
+    let data = [1.0f64, 2., 3., 4.];
+    let x = [1.0f64, 10., 100.];
+    let mut y = [1.0f64, 1., 1.];
+    spmv![y = 2. * csr(indptr, indices, data) * x + 0.5 * y];
+    assert_eq!(y, [402.5, 0.5, 860.5]);
+    spmv![y -= csr(indptr, indices, data) * x];
+    assert_eq!(y, [201.5, 0.5, 430.5]);
+}
+
+#[test]
+fn tridiagonal() {
+    let x = [1., 2., 3., 4., 5.];
+    let dl = [9., -1., -1., -1., -1.];
+    let d = [2.; 5];
+    let du = [-1., -1., -1., -1., 9.];
+    let mut y = [0.; 5];
+    tridiagmv![y = dl*shift(x, -1) + d*x + du*shift(x, +1)];
+    assert_eq!(y, [0., 0., 0., 0., 6.]);
+    // a pentadiagonal operator, accumulated and subtracted
+    let e = [1.; 5];
+    tridiagmv![y += e*shift(x, -2) - e*shift(x, 2)];
+    assert_eq!(y, [-3., -4., -4., 2., 9.]);
+}
+
+#[test]
+fn panels() {
+    // a 3x4 and a 3x5 matrix, row-major
+    let a = [1., 2., 3., 4.,
+             5., 6., 7., 8.,
+             9., 10., 11., 12.];
+    let mut c = [0.; 15];
+    let b = [1., 1., 2., 2.];
+    {
+        // the 2x2 block of c at (1, 2) from the one of a at (1, 1), plus a packed 2x2 b
+        let c = &mut c[5 + 2..];
+        let a = &a[4 + 1..];
+        axpy![panel(c, 2, 2, 5) = 2.*panel(a, 2, 3, 4) + b];
+    }
+    assert_eq!(c, [0., 0., 0., 0., 0.,
+                   0., 0., 13., 15., 0.,
+                   0., 0., 22., 24., 0.]);
+}
@@ -0,0 +1,20 @@
+#[macro_use] extern crate axpy;
+
+type Update = Box<dyn Fn(&mut [f64], &[f64])>;
+
+#[test]
+fn stored_combination() {
+    let (a, b) = (2f64, -1f64);
+    let update = lincomb![a*x + b*y - x];
+    let (x, y) = ([1., 2., 3.], [1., 1., 1.]);
+    let mut z = [0f64; 3];
+    update(&mut z, &x, &y);
+    assert_eq!(z, [0., 1., 2.]);
+    let w = vec![5f64; 3];
+    update(&mut z, &w, &x);
+    assert_eq!(z, [4., 3., 2.]);
+
+    let boxed: Update = Box::new(lincomb![0.5*v]);
+    boxed(&mut z, &w);
+    assert_eq!(z, [2.5; 3]);
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "memmap")]
+#[macro_use] extern crate axpy;
+
+use axpy::mmap;
+
+// stand-in for a mapped file: bytes aligned for f64, as a (page-aligned) mapping is
+fn mapping(x: &[f64]) -> Vec<u64> {
+    x.iter().map(|x| x.to_bits()).collect()
+}
+
+fn bytes(x: &mut [u64]) -> &mut [u8] {
+    unsafe { std::slice::from_raw_parts_mut(x.as_mut_ptr() as *mut u8, x.len() * 8) }
+}
+
+#[test]
+fn views() {
+    let mut x = mapping(&[1., 2., 3.]);
+    assert_eq!(mmap::view::<f64>(bytes(&mut x)), [1., 2., 3.]);
+    mmap::view_mut::<f64>(bytes(&mut x))[1] = 5.;
+    assert_eq!(f64::from_bits(x[1]), 5.);
+    assert!(std::panic::catch_unwind(|| mmap::view::<f64>(&[0u8; 12])).is_err());
+}
+
+#[test]
+fn chunked() {
+    // a little over two chunks
+    let n = 2 * mmap::CHUNK / 8 + 3;
+    let mut xs = mapping(&(0..n).map(|i| i as f64).collect::<Vec<_>>());
+    let mut ys = mapping(&vec![1.; n]);
+    let mut zs = mapping(&vec![0.; n]);
+    let x = mmap::view::<f64>(bytes(&mut xs));
+    let y = mmap::view::<f64>(bytes(&mut ys));
+    let z = mmap::view_mut::<f64>(bytes(&mut zs));
+    let a = 2.;
+    axpy![mmap: z = a*x + y];
+    assert!(z.iter().enumerate().all(|(i, z)| *z == 2. * i as f64 + 1.));
+}
@@ -0,0 +1,72 @@
+#[macro_use] extern crate axpy;
+
+/// A stand-in for `ndarray::Array2`, with the methods that `nd:` uses, stored row-major or
+/// column-major.
+struct Grid {
+    data: Vec<f64>,
+    shape: [usize; 2],
+    strides: [isize; 2],
+}
+
+impl Grid {
+    fn new(shape: [usize; 2], fortran: bool, f: impl Fn(usize, usize) -> f64) -> Grid {
+        let strides = if fortran { [1, shape[0] as isize] } else { [shape[1] as isize, 1] };
+        let mut data = vec![0.; shape[0] * shape[1]];
+        for i in 0..shape[0] {
+            for j in 0..shape[1] {
+                data[i * strides[0] as usize + j * strides[1] as usize] = f(i, j);
+            }
+        }
+        Grid { data, shape, strides }
+    }
+    fn contiguous(&self) -> bool {
+        self.data.len() == self.shape[0] * self.shape[1]
+    }
+    fn shape(&self) -> &[usize] { &self.shape }
+    fn strides(&self) -> &[isize] { &self.strides }
+    fn as_slice_memory_order(&self) -> Option<&[f64]> {
+        if self.contiguous() { Some(&self.data) } else { None }
+    }
+    fn as_slice_memory_order_mut(&mut self) -> Option<&mut [f64]> {
+        if self.contiguous() { Some(&mut self.data) } else { None }
+    }
+    fn at(&self, i: usize, j: usize) -> f64 {
+        self.data[i * self.strides[0] as usize + j * self.strides[1] as usize]
+    }
+    fn offsets(&self) -> Vec<usize> {
+        let mut o = vec![];
+        for i in 0..self.shape[0] {
+            for j in 0..self.shape[1] {
+                o.push(i * self.strides[0] as usize + j * self.strides[1] as usize);
+            }
+        }
+        o
+    }
+    fn iter(&self) -> impl Iterator<Item = &f64> {
+        self.offsets().into_iter().map(move |k| &self.data[k])
+    }
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut f64> {
+        let offsets = self.offsets();
+        let mut refs: Vec<Option<&mut f64>> = self.data.iter_mut().map(Some).collect();
+        offsets.into_iter().map(move |k| refs[k].take().unwrap()).collect::<Vec<_>>().into_iter()
+    }
+}
+
+#[test]
+fn memory_orders() {
+    let x = Grid::new([2, 3], false, |i, j| (3 * i + j) as f64);
+    let y = Grid::new([2, 3], true, |i, j| (10 * i + j) as f64);
+    let mut z = Grid::new([2, 3], false, |_, _| 1.);
+    // z and x share a memory order, y does not, so this takes the element iterators ...
+    axpy![nd: z = 2.*x + y - z];
+    // ... and this the flat slices
+    axpy![nd: z += x];
+    for i in 0..2 {
+        for j in 0..3 {
+            assert_eq!(z.at(i, j), 3. * (3 * i + j) as f64 + (10 * i + j) as f64 - 1.);
+        }
+    }
+    let mut w = Grid::new([2, 3], true, |_, _| 0.);
+    axpy![nd: w = y];
+    assert_eq!(w.data, y.data);
+}
@@ -0,0 +1,20 @@
+#[macro_use] extern crate axpy;
+
+use std::num::{Saturating, Wrapping};
+
+#[test]
+fn wrapping() {
+    let x = [Wrapping(i32::MAX), Wrapping(1), Wrapping(-3)];
+    let mut y = [Wrapping(1), Wrapping(2), Wrapping(i32::MIN)];
+    let a = 2;
+    axpy![y = a*x + y - x];
+    assert_eq!(y, [Wrapping(i32::MIN), Wrapping(3), Wrapping(i32::MAX - 2)]);
+}
+
+#[test]
+fn saturating() {
+    let x = [Saturating(i16::MAX), Saturating(1), Saturating(-3)];
+    let mut y = [Saturating(1i16), Saturating(2), Saturating(i16::MIN)];
+    axpy![y += 3*x];
+    assert_eq!(y, [Saturating(i16::MAX), Saturating(5), Saturating(i16::MIN)]);
+}
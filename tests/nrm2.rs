@@ -0,0 +1,31 @@
+#![cfg(feature = "std")]
+#[macro_use] extern crate axpy;
+
+#[test]
+fn scaled() {
+    let x = [3f64, -4.];
+    assert_eq!(nrm2![x], 5.);
+    let big = [3e200f64, 4e200];
+    assert!((nrm2![big] / 5e200 - 1.).abs() < 1e-15);
+    let tiny = [3e-200f64, -4e-200];
+    assert!((nrm2![tiny] / 5e-200 - 1.).abs() < 1e-15);
+    let mixed = [1e-160f64, 1., 1e-170];
+    assert_eq!(nrm2![mixed], 1.);
+    let f = [3e30f32, 4e30];
+    assert!((nrm2![f] / 5e30 - 1.).abs() < 1e-6);
+    let empty: [f32; 0] = [];
+    assert_eq!(nrm2![empty], 0.);
+}
+
+#[test]
+fn compensated() {
+    let mut x: Vec<f32> = vec![1e-4; 1 << 16];
+    x[0] = 1.;
+    assert_eq!(nrm2![x], 1.);
+    assert!((nrm2![kahan: x] - 1.000_328).abs() < 1e-6);
+    assert!((nrm2![neumaier: x] - 1.000_328).abs() < 1e-6);
+    let big = [3e200f64, 4e200];
+    assert!((nrm2![kahan: big] / 5e200 - 1.).abs() < 1e-15);
+    let tiny = [3e-200f64, -4e-200];
+    assert!((nrm2![neumaier: tiny] / 5e-200 - 1.).abs() < 1e-15);
+}
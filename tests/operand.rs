@@ -0,0 +1,92 @@
+#[macro_use] extern crate axpy;
+
+use axpy::{AxpySource, AxpyTarget};
+
+/// A buffer without inherent `iter`/`iter_mut`.
+struct Buffer { data: [f32; 4] }
+
+impl<'a> AxpySource<'a> for Buffer {
+    type Iter = std::slice::Iter<'a, f32>;
+    fn iter(&'a self) -> Self::Iter { self.data.iter() }
+}
+
+impl<'a> AxpyTarget<'a> for Buffer {
+    type IterMut = std::slice::IterMut<'a, f32>;
+    fn iter_mut(&'a mut self) -> Self::IterMut { self.data.iter_mut() }
+}
+
+#[test]
+fn custom_container() {
+    let x = Buffer { data: [1., 2., 3., 4.] };
+    let mut y = Buffer { data: [1.; 4] };
+    let z: [f32; 4] = [0.5; 4];
+    axpy![truncate: y = 2.*x + y - z];
+    assert_eq!(y.data, [2.5, 4.5, 6.5, 8.5]);
+}
+
+/// Stand-in for `SmallVec<[f32; 4]>`: inline up to 4 elements, spilled to the heap beyond.
+enum Small { Inline([f32; 4], usize), Heap(Vec<f32>) }
+
+impl Small {
+    fn from(x: &[f32]) -> Small {
+        if x.len() <= 4 {
+            let mut a = [0.; 4];
+            a[..x.len()].copy_from_slice(x);
+            Small::Inline(a, x.len())
+        } else {
+            Small::Heap(x.to_vec())
+        }
+    }
+}
+
+impl std::ops::Deref for Small {
+    type Target = [f32];
+    fn deref(&self) -> &[f32] {
+        match *self { Small::Inline(ref a, n) => &a[..n], Small::Heap(ref v) => v }
+    }
+}
+
+impl std::ops::DerefMut for Small {
+    fn deref_mut(&mut self) -> &mut [f32] {
+        match *self { Small::Inline(ref mut a, n) => &mut a[..n], Small::Heap(ref mut v) => v }
+    }
+}
+
+#[test]
+fn deref_to_slice() {
+    for n in [3, 6] {
+        let x = Small::from(&[1., 2., 3., 4., 5., 6.][..n]);
+        let mut y = Small::from(&[1.; 6][..n]);
+        axpy![strict: y = 2.*x + y];
+        assert_eq!(&y[..], &[3., 5., 7., 9., 11., 13.][..n]);
+    }
+}
+
+#[test]
+fn smart_pointers() {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::thread;
+
+    let b: Box<[f64]> = vec![1., 2.].into_boxed_slice();
+    let r: Rc<[f64]> = Rc::from(vec![3., 4.]);
+    let c: Cow<[f64]> = Cow::Borrowed(&[5., 6.]);
+    let mut z: Box<[f64]> = vec![0.; 2].into_boxed_slice();
+    axpy![z = b + 2.*r - c];
+    assert_eq!(*z, [2., 4.]);
+    axpy![strict: z += c];
+    assert_eq!(*z, [7., 10.]);
+
+    let shared: Arc<[f64]> = Arc::from(vec![1., 1.]);
+    let handles: Vec<_> = (0..2).map(|k| {
+        let x = Arc::clone(&shared);
+        thread::spawn(move || {
+            let mut y = vec![k as f64; 2];
+            axpy![y += 2.*x];
+            y
+        })
+    }).collect();
+    let ys: Vec<Vec<f64>> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(ys, [[2., 2.], [3., 3.]]);
+}
@@ -0,0 +1,12 @@
+#![cfg(feature = "alloc")]
+#[macro_use] extern crate axpy;
+
+#[test]
+fn new_vec() {
+    let x: [f64; 4] = [1., 2., 3., 4.];
+    let y: [f64; 3] = [4., 3., 2.];
+    let z: Vec<f64> = axpy_new![2.*x - y + sqrt(x)];
+    assert_eq!(z, vec![-1., 1.+2f64.sqrt(), 4.+3f64.sqrt()]);
+    let w = axpy_new![max(x, y)];
+    assert_eq!(w, vec![4., 3., 3.]);
+}
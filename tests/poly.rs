@@ -0,0 +1,19 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn polyval_horner() {
+    let x: [i32; 4] = [0, 1, 2, -1];
+    let mut y: [i32; 4] = [0; 4];
+    polyval![y = 1 + 2*x + 3*x^2 + 4*x^3];
+    assert_eq!(y, [1, 10, 49, -2]);
+    polyval![y = 1 + 1*y];
+    assert_eq!(y, [2, 11, 50, -1]);
+}
+
+#[test]
+fn polyval_fma() {
+    let x: [f64; 3] = [0., 0.5, 2.];
+    let mut y: [f64; 3] = [0.; 3];
+    polyval![fma: y = 1. + 2.*x + 4.*x^2];
+    assert_eq!(y, [1., 3., 21.]);
+}
@@ -0,0 +1,31 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn form_raw_pointers() {
+    let x: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+    let y: [f64; 4] = [4.0, 3.0, 2.0, 1.0];
+    let mut z: [f64; 4] = [10., 100., 1000., 10000.];
+    let (px, py, pz): (*const f64, *const f64, *mut f64) = (x.as_ptr(), y.as_ptr(), z.as_mut_ptr());
+    unsafe { axpy![ptr(3): pz = 2.*pz - px + 3. * py]; }
+    assert_eq!(z, [31f64, 207., 2003., 10000.]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the operand `x` (")]
+fn form_raw_pointers_overlap() {
+    let mut z: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+    let pz: *mut f64 = z.as_mut_ptr();
+    let x: *const f64 = unsafe { pz.add(1) };
+    unsafe { axpy![ptr(3): pz = pz + x]; }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the operand `py` (")]
+fn swap_raw_pointers_overlap() {
+    let mut x: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+    let px: *mut f64 = x.as_mut_ptr();
+    let py: *mut f64 = unsafe { px.add(2) };
+    unsafe { swap![ptr(3): px, py]; }
+}
@@ -0,0 +1,16 @@
+#[macro_use] extern crate axpy;
+
+use axpy::quant::Quant;
+
+#[test]
+fn residual_add() {
+    // x = [0.5, 1.0, -1.0, 63.5], y = [1.0, 2.0, 3.0, 12.7]
+    let x = Quant::new(vec![1i8, 2, -2, 127], 0.5, 0);
+    let y = Quant::new(&[138u8, 148, 158, 255][..], 0.1, 128);
+    let mut z = Quant::new([0i8; 4], 0.25, -10);
+    axpy![quant: z = x + 0.5*y];
+    // 1.0, 2.0, 0.5 and 69.85 (saturated) in quarters above -10
+    assert_eq!(z.data, [-6, -2, -8, 127]);
+    axpy![quant: z -= x];
+    assert_eq!(z.data, [-8, -6, -4, -127]);
+}
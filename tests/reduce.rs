@@ -0,0 +1,204 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn dot_plain_and_weighted() {
+    let x: [f64; 3] = [1., 2., 3.];
+    let w: [f64; 3] = [0.5, 1., 2.];
+    let y: [f64; 3] = [2., 2., 2.];
+    assert_eq!(dot![x, y], 12.);
+    assert_eq!(dot![x, w, y], 17.);
+    let i: [i32; 2] = [3, -4];
+    assert_eq!(dot![i, i], 25);
+}
+
+#[test]
+fn dot_compensated() {
+    // 1 + 1e-16 + 1e-16 + ... loses every small term when summed naively
+    let mut x: Vec<f64> = vec![1e-16; 1001];
+    x[0] = 1.;
+    let y: Vec<f64> = vec![1.; 1001];
+    assert_eq!(dot![x, y], 1.);
+    assert_eq!(dot![kahan: x, y], 1. + 1e-13);
+    assert_eq!(dot![neumaier: x, y, y], 1. + 1e-13);
+}
+
+#[test]
+fn dot_error_bound() {
+    // the 1 is lost next to 1e16, and the bound says by how much that may be
+    let x: [f64; 3] = [1e16, 1., -1e16];
+    let y: [f64; 3] = [1., 1., 1.];
+    let (d, err) = dot![bound: x, y];
+    assert_eq!(d, 0.);
+    assert!((1. ..10.).contains(&err));
+    let (d, err) = dot![bound: y, y, y];
+    assert_eq!(d, 3.);
+    assert_eq!(err, f64::EPSILON / 2. * (1. + 2. + 3. + 2. * 3.));
+}
+
+#[test]
+fn dot_pairwise() {
+    let x: Vec<i64> = (1..=1000).collect();
+    let y: Vec<i64> = vec![1; 1000];
+    assert_eq!(dot![pairwise: x, y], 500500);
+    assert_eq!(dot![pairwise(7): x, y, y], 500500);
+    assert_eq!(dot![pairwise(1000): x, y], 500500);
+    let mut f: Vec<f32> = vec![1e-8; 1 << 16];
+    f[0] = 1.;
+    let ones: Vec<f32> = vec![1.; 1 << 16];
+    assert_eq!(dot![f, ones], 1.);
+    assert!((dot![pairwise(16): f, ones] - 1.000_655).abs() < 1e-6);
+}
+
+#[test]
+fn dot_lanes() {
+    let x: Vec<i64> = (1..=1001).collect();
+    let y: Vec<i64> = vec![1; 1001];
+    assert_eq!(dot![lanes: x, y], 501501);
+    assert_eq!(dot![lanes(3): x, y, y], 501501);
+    let e: [f64; 0] = [];
+    assert_eq!(dot![lanes(4): e, e], 0.);
+}
+
+#[test]
+fn dot_lanes_remainder() {
+    // 13 = 3 * 4 + 1, so the last product is added by the remainder loop
+    let x: Vec<f64> = (1..=13).map(|i| i as f64).collect();
+    let y: Vec<f64> = vec![0.5; 13];
+    assert_eq!(dot![lanes(4): x, y], 45.5);
+    assert_eq!(dot![lanes(4): x, y, y], 22.75);
+    // shorter than a single window
+    assert_eq!(dot![lanes(16): x, y], 45.5);
+    // the longer operand is cut to the shorter one, as with `zip`
+    let z: Vec<f64> = vec![1.; 6];
+    assert_eq!(dot![lanes(4): x, z], 21.);
+}
+
+#[test]
+fn dot_streaming() {
+    let x: Vec<f64> = (0..100).map(|i| i as f64).collect();
+    let y: Vec<f64> = vec![2.; 100];
+    let mut acc = axpy::acc::DotAcc::new();
+    let mut other = axpy::acc::DotAcc::new();
+    for (xs, ys) in x[..60].chunks(16).zip(y[..60].chunks(16)) {
+        dot![acc += xs, ys];
+    }
+    for (xs, ys) in x[60..].chunks(7).zip(y[60..].chunks(7)) {
+        dot![other += xs, ys, ys];
+    }
+    acc.merge(other);
+    assert_eq!(acc.finish(), 2. * 1770. + 4. * 3180.);
+}
+
+#[test]
+fn absolute_sums() {
+    let x: [f64; 3] = [1., -2., 3.5];
+    let y: [f64; 3] = [2., 2., -0.5];
+    assert_eq!(asum![x], 6.5);
+    assert_eq!(asum![x - y], 9.);
+    assert_eq!(asum![2.*x + y], 12.5);
+    let i: [i32; 2] = [-3, 4];
+    assert_eq!(asum![i], 7);
+}
+
+#[test]
+fn absolute_sums_compensated() {
+    let mut f: Vec<f32> = vec![-1e-8; 1 << 16];
+    f[0] = 1.;
+    assert_eq!(asum![f], 1.);
+    assert!((asum![kahan: f] - 1.000_655).abs() < 1e-6);
+    assert!((asum![neumaier: f] - 1.000_655).abs() < 1e-6);
+    let ones: Vec<f32> = vec![1.; 1 << 16];
+    assert_eq!(asum![kahan: f - ones], 65535.);
+}
+
+#[test]
+fn extremal_indices() {
+    let x: [f64; 5] = [1., -4., 2., 4., f64::NAN];
+    let y: [f64; 5] = [0., 0., 5., 0., 0.];
+    assert_eq!(iamax![x], Some(1));
+    assert_eq!(iamin![x], Some(0));
+    assert_eq!(iamax![x - y], Some(1));
+    assert_eq!(iamin![x - y], Some(0));
+    assert_eq!(iamax![x + y], Some(2));
+    let nan = [f64::NAN; 2];
+    assert_eq!(iamax![nan], Some(0));
+    let empty: [i32; 0] = [];
+    assert_eq!(iamin![empty], None);
+}
+
+#[test]
+fn extremal_values() {
+    let x: [f64; 3] = [1., -4., 2.];
+    let y: [f64; 3] = [1.5, -1., 2.];
+    assert_eq!(max![x], Some(2.));
+    assert_eq!(min![x - y], Some(-3.));
+    assert_eq!(max![abs(x - y)], Some(3.));
+    let n: [f64; 3] = [1., f64::NAN, 3.];
+    assert!(max![n].unwrap().is_nan());
+    assert!(min![abs(n - x)].unwrap().is_nan());
+    assert_eq!(max![ignore_nan: n], Some(3.));
+    assert_eq!(min![ignore_nan: n + x], Some(2.));
+    let nan = [f64::NAN];
+    assert_eq!(max![ignore_nan: nan], None);
+    let empty: [i32; 0] = [];
+    assert_eq!(min![empty], None);
+}
+
+#[test]
+fn statistics() {
+    // a large offset would wreck the sum-of-squares formula
+    let x: Vec<f64> = (0..11).map(|i| 1e9 + i as f64).collect();
+    let s = stats![x];
+    assert_eq!(s.count(), 11);
+    assert_eq!(s.mean(), 1e9 + 5.);
+    assert_eq!(s.variance(), 10.);
+    assert_eq!(s.sample_variance(), 11.);
+
+    let y: Vec<f64> = vec![1e9; 11];
+    let mut acc = axpy::acc::Stats::new();
+    for (xs, ys) in x.chunks(3).zip(y.chunks(3)) {
+        stats![acc += 2.*xs - 2.*ys];
+    }
+    assert_eq!(acc.count(), 11);
+    assert_eq!(acc.mean(), 10.);
+    assert_eq!(acc.variance(), 40.);
+}
+
+#[test]
+fn per_block() {
+    let x: Vec<f64> = (1..=10).map(|i| i as f64).collect();
+    let y = [1.; 10];
+    let mut energy = [0.; 4];
+    dot![blocks(4, energy): x, x];
+    assert_eq!(energy, [30., 174., 181., 0.]);
+    let mut dist = [0.; 2];
+    asum![blocks(5, dist): x - 2.*y];
+    assert_eq!(dist, [1. + 0. + 1. + 2. + 3., 4. + 5. + 6. + 7. + 8.]);
+    let mut peak = [None; 3];
+    max![blocks(3, peak): abs(y - x)];
+    assert_eq!(peak, [Some(2.), Some(5.), Some(8.)]);
+}
+
+#[test]
+fn dot_of_combinations() {
+    let x = [1., 2., 3.];
+    let y = [1., 1., 1.];
+    let z = [2., 0., -1.];
+    let w = [0., 1., 2.];
+    let (a, b) = (2., 0.5);
+    assert_eq!(dot![a*x + y, z - b*w], 3. * 2. + 5. * -0.5 + 7. * -2.);
+    assert_eq!(dot![kahan: x - y, x - y], 5.);
+    // weighted, with a plain operand in between
+    assert_eq!(dot![x + y, w, x - y], 0. + 3. * 1. * 1. + 4. * 2. * 2.);
+}
+
+#[test]
+fn widened() {
+    let w: [i8; 4] = [100, -100, 127, -128];
+    let x: [i8; 4] = [100, 100, 127, -128];
+    assert_eq!(dot![widen(i32): w, x], 32513);
+    let (sw, sx) = (0.5f32, 0.25f32);
+    assert_eq!(dot![widen(i32, sw * sx): w, x], 4064.125);
+    let g: [u8; 4] = [255, 0, 1, 2];
+    assert_eq!(dot![widen(i64): w, g, x], 2550000 + 16129 + 32768);
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "alloc")]
+#[macro_use] extern crate axpy;
+
+use std::collections::VecDeque;
+
+// a deque of 0..n whose storage wraps around after `head` elements
+fn wrapped(n: usize, head: usize) -> VecDeque<f64> {
+    let mut d = VecDeque::with_capacity(n);
+    for k in (0..head).rev() {
+        d.push_front(k as f64);
+    }
+    for k in head..n {
+        d.push_back(k as f64);
+    }
+    d
+}
+
+#[test]
+fn deques() {
+    let x = wrapped(6, 2);
+    let mut y = wrapped(6, 4);
+    assert!(!x.as_slices().1.is_empty() || !y.as_slices().1.is_empty());
+    let w = [1f64; 6];
+    axpy![ring: y = 2.*x + y - w];
+    assert!(y.iter().eq([-1., 2., 5., 8., 11., 14.].iter()));
+
+    // the shortest operand decides
+    let mut z = [0f64; 8];
+    axpy![ring: z = x];
+    assert_eq!(z, [0., 1., 2., 3., 4., 5., 0., 0.]);
+}
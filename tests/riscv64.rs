@@ -0,0 +1,24 @@
+#![cfg(all(feature = "arch-riscv64", target_arch = "riscv64", target_feature = "v"))]
+#[macro_use] extern crate axpy;
+
+#[test]
+fn kernels() {
+    // lengths up to a few strips at the smallest vector length, so that short last strips are covered
+    for n in 0..70 {
+        let x: Vec<f64> = (0..n).map(|k| k as f64).collect();
+        let y: Vec<f64> = (0..n).map(|k| (k % 7) as f64).collect();
+        let (mut z, mut expected) = (vec![1f64; n], vec![1f64; n]);
+        axpy![riscv64: z = 2.*x - y];
+        axpy![expected = 2.*x - y];
+        assert_eq!(z, expected);
+        axpy![riscv64: z -= x - 0.5*y];
+        axpy![expected -= x - 0.5*y];
+        assert_eq!(z, expected);
+
+        let x: Vec<f32> = (0..n).map(|k| k as f32).collect();
+        let (mut z, mut expected) = (vec![1f32; n], vec![1f32; n]);
+        axpy![riscv64: z += 3.*x];
+        axpy![expected += 3.*x];
+        assert_eq!(z, expected);
+    }
+}
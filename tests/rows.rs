@@ -0,0 +1,85 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn rows_vec_of_vecs() {
+    let x: Vec<Vec<f64>> = vec![vec![1., 2.], vec![3., 4.]];
+    let y: Vec<Vec<f64>> = vec![vec![1., 1.], vec![2., 2.]];
+    let mut z: Vec<Vec<f64>> = vec![vec![0., 0.], vec![0., 0.]];
+    axpy![for rows: z = x + 2.0*y - x + z + x];
+    assert_eq!(z, vec![vec![3., 4.], vec![7., 8.]]);
+}
+
+#[test]
+fn rows_slices_of_slices() {
+    let (mut r0, mut r1) = ([1, 2, 3], [4, 5, 6]);
+    let x: [&[i32]; 2] = [&[1, 1, 1], &[2, 2, 2]];
+    let z: &mut [&mut [i32]] = &mut [&mut r0, &mut r1];
+    axpy![for rows: z -= x];
+    assert_eq!(r0, [0, 1, 2]);
+    assert_eq!(r1, [2, 3, 4]);
+}
+
+#[test]
+fn rows_of_flat_buffers() {
+    // three systems of two states each
+    let x: Vec<f64> = vec![1., 2., 3., 4., 5., 6.];
+    let y: Vec<f64> = vec![1.; 6];
+    let mut z: Vec<f64> = vec![0.; 6];
+    axpy![for rows(2): z = 2.*x + y];
+    assert_eq!(z, [3., 5., 7., 9., 11., 13.]);
+    axpy![for rows(3): z -= y];
+    assert_eq!(z, [2., 4., 6., 8., 10., 12.]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn rows_in_parallel() {
+    let x: Vec<f64> = (0..3000).map(|i| i as f64).collect();
+    let mut z: Vec<f64> = vec![1.; 3000];
+    let a = 2.;
+    axpy![par for rows(3): z = a*x - z];
+    assert!(z.iter().enumerate().all(|(i, &v)| v == 2. * i as f64 - 1.));
+}
+
+// a job system of the program's own, here one that runs the jobs in reverse and counts them
+struct Backwards(std::sync::atomic::AtomicUsize);
+
+unsafe impl axpy::exec::Executor for Backwards {
+    fn jobs(&self) -> usize { 4 }
+    fn run<F: Fn(usize) + Sync>(&self, n: usize, job: F) {
+        for k in (0..n).rev() {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            job(k);
+        }
+    }
+}
+
+#[test]
+fn custom_executor() {
+    let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+    let mut z: Vec<f64> = vec![1.; 10];
+    let exec = Backwards(Default::default());
+    axpy![par(exec): z = 2.*x - z];
+    assert_eq!(exec.0.into_inner(), 4);
+    assert!(z.iter().enumerate().all(|(i, &v)| v == 2. * i as f64 - 1.));
+    axpy![par(axpy::exec::Sequential) for rows(2): z += x];
+    assert!(z.iter().enumerate().all(|(i, &v)| v == 3. * i as f64 - 1.));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn partitioned_by_hand() {
+    let x: Vec<f64> = (0..100).map(|i| i as f64).collect();
+    let y: Vec<f64> = vec![1.; 100];
+    let mut z: Vec<f64> = vec![0.; 100];
+    let a = 2.;
+    let mut lengths = Vec::new();
+    std::thread::scope(|scope| {
+        for (z, x, y) in partition!(3, 8; z, x, y) {
+            lengths.push((z.len(), x.len(), y.len()));
+            scope.spawn(move || axpy![z = a*x + y]);
+        }
+    });
+    assert_eq!(lengths, [(40, 40, 40), (40, 40, 40), (20, 20, 20)]);
+    assert!(z.iter().enumerate().all(|(i, &v)| v == 2. * i as f64 + 1.));
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "rug")]
+#[macro_use] extern crate axpy;
+
+use std::cell::Cell;
+use std::ops::{AddAssign, DivAssign, MulAssign, Neg, SubAssign};
+
+thread_local!(static CLONES: Cell<usize> = const { Cell::new(0) });
+
+// a stand-in for `rug::Float`: a heap-allocated value, with the operations that `rug:` uses, whose
+// clones (allocations) are counted
+#[derive(Debug, PartialEq)]
+struct Float(Box<f64>);
+
+impl Clone for Float {
+    fn clone(&self) -> Float {
+        CLONES.with(|c| c.set(c.get() + 1));
+        Float(Box::new(*self.0))
+    }
+    fn clone_from(&mut self, x: &Float) { *self.0 = *x.0 }
+}
+
+impl AddAssign<&Float> for Float { fn add_assign(&mut self, x: &Float) { *self.0 += *x.0 } }
+impl SubAssign<&Float> for Float { fn sub_assign(&mut self, x: &Float) { *self.0 -= *x.0 } }
+impl MulAssign<&Float> for Float { fn mul_assign(&mut self, x: &Float) { *self.0 *= *x.0 } }
+impl MulAssign<f64> for Float { fn mul_assign(&mut self, a: f64) { *self.0 *= a } }
+impl DivAssign<f64> for Float { fn div_assign(&mut self, d: f64) { *self.0 /= d } }
+impl Neg for Float {
+    type Output = Float;
+    fn neg(mut self) -> Float { *self.0 = -*self.0; self }
+}
+
+fn floats(x: &[f64]) -> Vec<Float> {
+    x.iter().map(|&x| Float(Box::new(x))).collect()
+}
+
+#[test]
+fn in_place() {
+    let x = floats(&[1., 2., 3., 4.]);
+    let w = floats(&[4., 3., 2., 1.]);
+    let mut y = floats(&[1., 1., 1., 1.]);
+    let a = Float(Box::new(2.));
+    CLONES.with(|c| c.set(0));
+    axpy![rug: y = (&a)*x + y - w/2.];
+    assert_eq!(y, floats(&[1., 3.5, 6., 8.5]));
+    axpy![rug: y -= -x + 0.5*y];
+    assert_eq!(y, floats(&[1.5, 3.75, 6., 8.25]));
+    axpy![rug: y = -w];
+    assert_eq!(y, floats(&[-4., -3., -2., -1.]));
+    // two scratch values per statement, whatever the number of elements
+    assert_eq!(CLONES.with(|c| c.get()), 6);
+}
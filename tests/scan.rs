@@ -0,0 +1,30 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn one_pole() {
+    let x: [f64; 6] = [1., 0., 0., 2., 0., 0.];
+    let mut z: [f64; 6] = [0.; 6];
+    let s = scan![z = 0.5*z + 2.*x; 4.];
+    assert_eq!(z, [4., 2., 1., 4.5, 2.25, 1.125]);
+    assert_eq!(s, 1.125);
+    // continuing from the last output
+    let s = scan![z = 0.5*z - z; s];
+    assert_eq!(z, [-3.4375, -3.71875, -2.859375, -5.9296875, -5.21484375, -3.732421875]);
+    assert_eq!(s, z[5]);
+}
+
+#[test]
+fn cumulative_sum() {
+    let x: Vec<i64> = (1..=20).collect();
+    let mut y = [0; 20];
+    cumsum![y = x];
+    assert!(y.iter().enumerate().all(|(i, &v)| v == (i as i64 + 1) * (i as i64 + 2) / 2));
+    let s = cumsum![y = x; 100];
+    assert_eq!((y[0], y[9], s), (101, 155, 310));
+    let mut z = [1.5f64; 11];
+    let s = cumsum![z; 0.5];
+    assert_eq!(z.to_vec(), (1..=11).map(|i| 0.5 + 1.5 * i as f64).collect::<Vec<_>>());
+    assert_eq!(s, 17.);
+    cumsum![z];
+    assert_eq!(z[1], 2. + 3.5);
+}
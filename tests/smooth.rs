@@ -0,0 +1,27 @@
+#[macro_use] extern crate axpy;
+
+use axpy::smooth::Ramp;
+
+#[test]
+fn ramped_gain() {
+    let inp = [1f32; 4];
+    let aux = [2f32; 4];
+    let mix = 0.5;
+    let mut gain = Ramp::new(1f32);
+    let mut out = [0f32; 4];
+
+    // steady
+    let g = gain.frame(out.len());
+    axpy![out[i] = (g.at(i))*inp + mix*aux];
+    assert_eq!(out, [2.; 4]);
+
+    // gliding to 3 over the next frame, then staying there
+    gain.set(3.);
+    let g = gain.frame(out.len());
+    axpy![out[i] = (g.at(i))*inp + mix*aux];
+    assert_eq!(out, [2.5, 3., 3.5, 4.]);
+    assert_eq!(gain.value(), 3.);
+    let g = gain.frame(out.len());
+    axpy![out[i] = (g.at(i))*inp];
+    assert_eq!(out, [3.; 4]);
+}
@@ -0,0 +1,19 @@
+#[macro_use] extern crate axpy;
+
+struct State {
+    x: Vec<f64>,
+    v: Vec<f64>,
+}
+
+soa!(State: f64 { x, v });
+
+#[test]
+fn fields() {
+    let s1 = State { x: vec![1., 2.], v: vec![0., 1.] };
+    let d = State { x: vec![0., 1.], v: vec![-1., -1.] };
+    let mut s2 = State { x: vec![0.; 2], v: vec![0.; 2] };
+    let dt = 0.5;
+    axpy_soa![s2 = s1 + dt*d];
+    assert_eq!(s2.x, [1., 2.5]);
+    assert_eq!(s2.v, [-0.5, 0.5]);
+}
@@ -0,0 +1,36 @@
+#[macro_use] extern crate axpy;
+
+use axpy::stochastic::{Bf16, Rng};
+
+#[test]
+fn small_updates() {
+    // the step 1e-3 is an eighth of the spacing of bf16 at 1, so rounding to nearest keeps 1
+    let g = vec![Bf16::from_f32(1.); 256];
+    let mut w = vec![Bf16::from_f32(1.); 256];
+    let lr = 1e-3f32;
+    let mut rng = Rng::new(7);
+    for _ in 0..500 {
+        axpy![stochastic(rng): w -= lr*g];
+    }
+    let mean = w.iter().map(|w| w.to_f32()).sum::<f32>() / 256.;
+    assert!((mean - 0.5).abs() < 0.01, "{}", mean);
+
+    let mut w = vec![1f32; 4];
+    for _ in 0..500 {
+        let g: Vec<f32> = vec![1.; 4];
+        axpy![w -= lr*g];
+        w = w.iter().map(|&w| Bf16::from_f32(w).to_f32()).collect();
+    }
+    assert_eq!(w, [1.; 4]);
+}
+
+#[test]
+fn rounding() {
+    let mut rng = Rng::new(1);
+    let x = [Bf16::from_f32(3.)];
+    let mut z = [Bf16::default()];
+    axpy![stochastic(rng): z = 0.5*x];
+    assert_eq!(z[0].to_f32(), 1.5);
+    assert_eq!(Bf16::from_f32(1. + 1. / 256.).to_f32(), 1.);
+    assert_eq!(Bf16::from_f32(1. + 3. / 256.).to_f32(), 1. + 4. / 256.);
+}
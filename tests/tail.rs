@@ -0,0 +1,28 @@
+#[macro_use] extern crate axpy;
+
+fn reference(x: &[f64], y: &[f64]) -> Vec<f64> {
+    x.iter().zip(y).map(|(x, y)| 2. * x - y).collect()
+}
+
+#[test]
+fn strategies() {
+    let x: Vec<f64> = (0..11).map(|k| k as f64).collect();
+    let y: Vec<f64> = (0..11).map(|k| (k * k) as f64).collect();
+    let expected = reference(&x, &y);
+    let mut z = [0f64; 11];
+    axpy![tail(scalar, 4): z = 2.*x - y];
+    assert_eq!(z[..], expected[..]);
+    z = [0.; 11];
+    axpy![tail(overlap, 4): z = 2.*x - y];
+    assert_eq!(z[..], expected[..]);
+    z = [0.; 11];
+    axpy![tail(masked, 4): z = 2.*x - y];
+    assert_eq!(z[..], expected[..]);
+
+    // shorter than a single chunk, and reading the target
+    let mut w = [1f64, 2., 3.];
+    axpy![tail(masked, 8): w += x];
+    assert_eq!(w, [1., 3., 5.]);
+    axpy![tail(overlap, 8): w = 2.*x];
+    assert_eq!(w, [0., 2., 4.]);
+}
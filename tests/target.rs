@@ -0,0 +1,34 @@
+#[macro_use] extern crate axpy;
+
+struct Solver {
+    residual: Vec<f64>,
+}
+
+impl Solver {
+    fn step(&mut self, a: f64, x: &[f64]) {
+        axpy![self.residual = a*x - 0.5*self.residual];
+    }
+}
+
+#[test]
+fn field() {
+    let mut s = Solver { residual: vec![2., 4.] };
+    s.step(2., &[1., 1.]);
+    assert_eq!(s.residual, [1., 0.]);
+    axpy![s.residual .*= s.residual];
+    assert_eq!(s.residual, [1., 0.]);
+}
+
+#[test]
+fn indexed() {
+    let x = [1., 2.];
+    let mut buffers = vec![vec![0.; 2]; 3];
+    let k = 1;
+    axpy![(buffers[k]) = 2.*x];
+    axpy![buffers[k + 1] -= x + buffers[k + 1]];
+    assert_eq!(buffers, [[0., 0.], [2., 4.], [-1., -2.]]);
+
+    let mut scratch = [1.; 4];
+    axpy![&mut scratch[1..3] += 3.*x];
+    assert_eq!(scratch, [1., 4., 7., 1.]);
+}
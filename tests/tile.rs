@@ -0,0 +1,74 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn tiled() {
+    let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+    let w: Vec<f64> = vec![1.; 8];
+    let mut y: Vec<f64> = vec![1.; 10];
+    let mut z: Vec<f64> = vec![0.; 10];
+    let a = 2.;
+    axpy![tile(3):
+        y = a*x + y;
+        z = y - w;
+    ];
+    let mut y2: Vec<f64> = vec![1.; 10];
+    let mut z2: Vec<f64> = vec![0.; 10];
+    axpy![y2 = a*x + y2];
+    axpy![truncate: z2 = y2 - w];
+    assert_eq!(y, y2);
+    assert_eq!(z, z2);
+}
+
+#[test]
+fn temporaries() {
+    let n = 150;
+    let k1: Vec<f64> = (0..n).map(|i| i as f64).collect();
+    let mut y: Vec<f64> = vec![1.; n];
+    let h = 0.5;
+    axpy!{
+        let k2 = y + (0.5*h)*k1;
+        let k3 = 2.*k2 - k1;
+        y += (h/4.)*k1 + (h/2.)*k2 - k3;
+    }
+    for (i, y) in y.iter().enumerate() {
+        let k2 = 1. + 0.25 * i as f64;
+        assert_eq!(*y, 1. + 0.125 * i as f64 + 0.25 * k2 - (2. * k2 - i as f64));
+    }
+}
+
+#[test]
+fn temporaries_in_registers() {
+    let x: Vec<f32> = (0..37).map(|i| i as f32).collect();
+    let mut y = [1f32; 37];
+    let mut z = [0f32; 37];
+    axpy!{block(8):
+        let t = 2.*x + y;
+        y = t - x;
+        z += t;
+    }
+    let mut w = [0f32; 37];
+    axpy!{block(1): let t = x; w = t + z; }
+    for i in 0..37 {
+        assert_eq!((y[i], z[i], w[i]), (i as f32 + 1., 2. * i as f32 + 1., 3. * i as f32 + 1.));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hooks() {
+    use std::sync::Mutex;
+    let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+    let mut y = [1.; 10];
+    let mut z = [0.; 10];
+    let chunks = Mutex::new(Vec::new());
+    let record = |s: usize, n: usize, _: std::time::Duration| chunks.lock().unwrap().push((s, n));
+    axpy![hook(record): tile(4): y = 2.*x + y; z = y - x];
+    assert_eq!(*chunks.lock().unwrap(), [(0, 4), (4, 4), (8, 2)]);
+    chunks.lock().unwrap().clear();
+    axpy!{hook(record): block(8): let t = x + y; z += t; }
+    assert_eq!(*chunks.lock().unwrap(), [(0, 8), (8, 2)]);
+    chunks.lock().unwrap().clear();
+    axpy![hook(record): par(axpy::exec::Sequential): z -= x];
+    assert_eq!(*chunks.lock().unwrap(), [(0, 10)]);
+    assert!((0..10).all(|i| y[i] == 2. * i as f64 + 1. && z[i] == 3. * i as f64 + 2.));
+}
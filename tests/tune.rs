@@ -0,0 +1,24 @@
+#![cfg(feature = "std")]
+#[macro_use] extern crate axpy;
+
+fn step(y: &mut [f64], z: &mut [f64], x: &[f64]) {
+    axpy![tile(auto):
+        y = 2.*x + y;
+        z = y - x;
+    ];
+}
+
+#[test]
+fn auto_tiled() {
+    let x: Vec<f64> = (0..5000).map(|i| i as f64).collect();
+    let mut y: Vec<f64> = vec![0.; 5000];
+    let mut z: Vec<f64> = vec![0.; 5000];
+    // runs past the trials, each one still performed exactly once
+    for _ in 0..12 {
+        step(&mut y, &mut z, &x);
+    }
+    for i in 0..5000 {
+        assert_eq!(y[i], 24. * i as f64);
+        assert_eq!(z[i], 23. * i as f64);
+    }
+}
@@ -0,0 +1,29 @@
+#[macro_use] extern crate axpy;
+
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+// Minimal stand-ins for `uom` quantities: only the products that make physical sense exist.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Length(f64);
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Velocity(f64);
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Time(f64);
+
+impl Mul<Velocity> for Time { type Output = Length; fn mul(self, v: Velocity) -> Length { Length(self.0 * v.0) } }
+impl Add for Length { type Output = Length; fn add(self, o: Length) -> Length { Length(self.0 + o.0) } }
+impl AddAssign for Length { fn add_assign(&mut self, o: Length) { self.0 += o.0 } }
+impl Sub for Length { type Output = Length; fn sub(self, o: Length) -> Length { Length(self.0 - o.0) } }
+
+#[test]
+fn dimensionful() {
+    let dt = Time(0.5);
+    let velocity = [Velocity(2.), Velocity(-4.)];
+    let mut position = [Length(1.), Length(1.)];
+    axpy![position += dt*velocity];
+    assert_eq!(position, [Length(2.), Length(-1.)]);
+
+    let start = [Length(10.), Length(0.)];
+    axpy![position = start + dt*velocity - position];
+    assert_eq!(position, [Length(9.), Length(-1.)]);
+}
@@ -0,0 +1,12 @@
+#[macro_use] extern crate axpy;
+
+#[test]
+fn dma_buffers() {
+    let rx: [i16; 4] = [1, -2, 3, -4];
+    let mut tx: [i16; 4] = [10, 20, 30, 40];
+    let (g, dc) = (3, [1i16; 4]);
+    axpy![volatile: tx = g*rx + dc - tx];
+    assert_eq!(tx, [-6, -25, -20, -51]);
+    axpy![volatile: tx += rx];
+    assert_eq!(tx, [-5, -27, -17, -55]);
+}
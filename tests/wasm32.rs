@@ -0,0 +1,24 @@
+#![cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[macro_use] extern crate axpy;
+
+#[test]
+fn kernels() {
+    // all lengths up to a few registers, so that every tail length is covered
+    for n in 0..12 {
+        let x: Vec<f64> = (0..n).map(|k| k as f64).collect();
+        let y: Vec<f64> = (0..n).map(|k| (k % 7) as f64).collect();
+        let (mut z, mut expected) = (vec![1f64; n], vec![1f64; n]);
+        axpy![wasm32: z = 2.*x - y];
+        axpy![expected = 2.*x - y];
+        assert_eq!(z, expected);
+        axpy![wasm32: z -= x - 0.5*y];
+        axpy![expected -= x - 0.5*y];
+        assert_eq!(z, expected);
+
+        let x: Vec<f32> = (0..n).map(|k| k as f32).collect();
+        let (mut z, mut expected) = (vec![1f32; n], vec![1f32; n]);
+        axpy![wasm32: z += 3.*x];
+        axpy![expected += 3.*x];
+        assert_eq!(z, expected);
+    }
+}
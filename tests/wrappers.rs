@@ -0,0 +1,18 @@
+extern crate axpy;
+
+use axpy::expr::{Assign, V, Y};
+
+#[test]
+fn operator_trees() {
+    let x = [1f64, 2., 3.];
+    let y = vec![1f64; 4];
+    let mut z = [1f64; 3];
+    z.assign(2.0*V(&x) + V(&y) - Y);
+    assert_eq!(z, [2., 4., 6.]);
+    z.assign(-V(&x) - 0.5*(Y - V(&y)));
+    assert_eq!(z, [-1.5, -3.5, -5.5]);
+
+    let mut n = vec![0i32; 2];
+    n.assign(3*V(&[1, 2]) - V(&[1, 1]));
+    assert_eq!(n, [2, 5]);
+}
@@ -0,0 +1,29 @@
+#![cfg(all(feature = "arch-x86", target_arch = "x86_64"))]
+#[macro_use] extern crate axpy;
+
+#[test]
+fn kernels() {
+    // all lengths up to a few AVX-512 registers, so that every tail length is covered
+    for n in 0..40 {
+        let x: Vec<f64> = (0..n).map(|k| k as f64).collect();
+        let y: Vec<f64> = (0..n).map(|k| (k % 7) as f64).collect();
+        let w: Vec<f64> = vec![2.; n];
+        let (mut z, mut expected) = (vec![1f64; n], vec![1f64; n]);
+        axpy![x86: z = 2.*x - y + w];
+        axpy![expected = 2.*x - y + w];
+        assert_eq!(z, expected);
+        let a = 0.5;
+        axpy![x86: z += a*x - 3.*y];
+        axpy![expected += a*x - 3.*y];
+        assert_eq!(z, expected);
+        axpy![x86: z -= x - a*w];
+        axpy![expected -= x - a*w];
+        assert_eq!(z, expected);
+
+        let x: Vec<f32> = (0..n).map(|k| k as f32).collect();
+        let (mut z, mut expected) = (vec![1f32; n], vec![1f32; n]);
+        axpy![x86: z += -x];
+        axpy![expected += -x];
+        assert_eq!(z, expected);
+    }
+}